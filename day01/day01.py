@@ -1,8 +1,8 @@
 
 
 # Part 1
-print(max([sum(map(int, group.splitlines())) for group in open('input/day01.in').read().split('\n\n')]))
+print(max([sum(map(int, group.splitlines())) for group in open('input/2022/day01.in').read().split('\n\n')]))
 
 # Part 2
 
-print(sum(sorted([sum(map(int, group.splitlines())) for group in open('input/day01.in').read().split('\n\n')], reverse=True)[0:3]))
\ No newline at end of file
+print(sum(sorted([sum(map(int, group.splitlines())) for group in open('input/2022/day01.in').read().split('\n\n')], reverse=True)[0:3]))
\ No newline at end of file
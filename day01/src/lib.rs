@@ -1,55 +1,394 @@
-use std::fs;
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    io::{self, BufRead},
+    num::ParseIntError,
+    str::FromStr,
+};
 
-pub fn part1() {
-    // read input
-    //split input into vector of strings on empty lines
+// one elf's snacks, ids count from 1 in the order the elves are listed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    pub id: usize,
+    pub items: Vec<u64>,
+}
+
+impl Elf {
+    pub fn calories(&self) -> u64 {
+        self.items.iter().sum()
+    }
+}
+
+// the k elves carrying the most seen so far, in a min-heap so the one to drop is on top.
+// elves carrying the same amount are ordered by id, the lower id wins
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    pub fn push(&mut self, id: usize, calories: u64) {
+        self.heap.push(Reverse((calories, Reverse(id))));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    // (id, calories), the most first
+    pub fn into_sorted(self) -> Vec<(usize, u64)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(id)))| (id, calories))
+            .collect()
+    }
+}
+
+// everything the elves carry, each group of lines is one elf
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalorieLedger {
+    elves: Vec<Elf>,
+}
+
+impl FromStr for CalorieLedger {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let elves = s
+            .split("\n\n")
+            .enumerate()
+            .map(|(i, group)| {
+                let items = group
+                    .lines()
+                    .map(|line| line.parse())
+                    .collect::<Result<_, _>>()?;
+                Ok(Elf { id: i + 1, items })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(CalorieLedger { elves })
+    }
+}
+
+impl CalorieLedger {
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    pub fn elf(&self, id: usize) -> Option<&Elf> {
+        self.elves.get(id.checked_sub(1)?)
+    }
+
+    // all the calories of all the elves
+    pub fn total(&self) -> u64 {
+        self.elves.iter().map(|elf| elf.calories()).sum()
+    }
+
+    // (id, calories) of the elf carrying the most
+    pub fn max(&self) -> Option<(usize, u64)> {
+        self.top_k(1).first().copied()
+    }
+
+    // (id, calories) of the k elves carrying the most, the most first
+    pub fn top_k(&self, k: usize) -> Vec<(usize, u64)> {
+        let mut top = TopK::new(k);
+        for elf in &self.elves {
+            top.push(elf.id, elf.calories());
+        }
+        top.into_sorted()
+    }
 
-    let input = fs::read_to_string("input/day01.in").expect("Error reading file");
-    let input_vec: Vec<&str> = input.split("\n\n").collect();
+    // the calories each elf carries, in order
+    pub fn totals(&self) -> Vec<u64> {
+        self.elves.iter().map(|elf| elf.calories()).collect()
+    }
 
-    let mut max = 0;
-    for group in input_vec {
-        //read every line in group and sum
-        let mut sum = 0;
-        let group_vec: Vec<&str> = group.split('\n').collect();
-        //cast to int and sum
-        for line in group_vec {
-            sum += line.parse::<i32>().unwrap();
+    // shares out every item again so the elf carrying the most carries as little as possible.
+    // starts from LPT and, for small inventories, searches for the best plan from there
+    pub fn plan(&self) -> Plan {
+        let mut items: Vec<u64> = self
+            .elves
+            .iter()
+            .flat_map(|elf| elf.items.iter().copied())
+            .collect();
+        items.sort_unstable_by(|a, b| b.cmp(a));
+        let elves = self.elves.len();
+
+        let assigned = lpt(&items, elves);
+        let mut loads = vec![0; elves];
+        for (item, elf) in items.iter().zip(&assigned) {
+            loads[*elf] += item;
         }
-        if sum > max {
-            max = sum;
+        let total: u64 = items.iter().sum();
+        let lower_bound = total
+            .div_ceil(elves.max(1) as u64)
+            .max(items.first().copied().unwrap_or(0));
+
+        let mut search = Search {
+            items: &items,
+            loads: vec![0; elves],
+            assigned: vec![0; items.len()],
+            best: loads.into_iter().max().unwrap_or(0),
+            best_assigned: assigned,
+            lower_bound,
+            steps: 0,
+        };
+        let optimal = search.best == lower_bound || (items.len() <= EXACT_ITEMS && search.run(0));
+
+        let mut loads = vec![Vec::new(); elves];
+        for (item, elf) in items.iter().zip(&search.best_assigned) {
+            loads[*elf].push(*item);
         }
+        Plan { loads, optimal }
+    }
+
+    // 1 for the elf carrying the most, elves carrying the same amount share a rank
+    pub fn rank_of(&self, id: usize) -> Option<usize> {
+        let calories = self.elf(id)?.calories();
+        let more = self
+            .elves
+            .iter()
+            .filter(|elf| elf.calories() > calories)
+            .count();
+        Some(more + 1)
+    }
+}
+
+// up to this many items the best plan is searched for, above it LPT has to do
+const EXACT_ITEMS: usize = 24;
+// and the search gives up after this many steps, keeping the best plan it found
+const SEARCH_STEPS: usize = 5_000_000;
+
+// a way of sharing out all the snacks between the same elves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    // the items each elf carries, in the same order as the ledger's elves
+    pub loads: Vec<Vec<u64>>,
+    // no plan can make the elf carrying the most carry less
+    pub optimal: bool,
+}
+
+impl Plan {
+    pub fn totals(&self) -> Vec<u64> {
+        self.loads.iter().map(|items| items.iter().sum()).collect()
+    }
+
+    pub fn max_load(&self) -> u64 {
+        self.totals().into_iter().max().unwrap_or(0)
     }
+}
+
+// how much more the elf carrying the most carries than the one carrying the least
+pub fn imbalance(totals: &[u64]) -> u64 {
+    let max = totals.iter().max().unwrap_or(&0);
+    let min = totals.iter().min().unwrap_or(&0);
+    max - min
+}
+
+// longest processing time first: the biggest item goes to the elf carrying the least,
+// which is never more than 4/3 of the best possible maximum
+fn lpt(items: &[u64], elves: usize) -> Vec<usize> {
+    let mut least: BinaryHeap<Reverse<(u64, usize)>> =
+        (0..elves).map(|elf| Reverse((0, elf))).collect();
+    items
+        .iter()
+        .map(|item| {
+            let Reverse((load, elf)) = least.pop().unwrap();
+            least.push(Reverse((load + item, elf)));
+            elf
+        })
+        .collect()
+}
+
+// depth first over which elf gets each item, biggest items first
+struct Search<'a> {
+    items: &'a [u64],
+    loads: Vec<u64>,
+    assigned: Vec<usize>,
+    // the smallest maximum found so far and the assignment that gets it
+    best: u64,
+    best_assigned: Vec<usize>,
+    // no assignment can do better than this, so the search can stop when it gets there
+    lower_bound: u64,
+    steps: usize,
+}
+
+impl Search<'_> {
+    // returns false if the search stopped before trying everything it had to
+    fn run(&mut self, item: usize) -> bool {
+        self.steps += 1;
+        if self.steps > SEARCH_STEPS {
+            return false;
+        }
+        if item == self.items.len() {
+            let max = *self.loads.iter().max().unwrap();
+            if max < self.best {
+                self.best = max;
+                self.best_assigned = self.assigned.clone();
+            }
+            return true;
+        }
 
-    println!("Max: {}", max);
+        for elf in 0..self.loads.len() {
+            let load = self.loads[elf] + self.items[item];
+            // only strictly better plans are interesting, and elves carrying the same
+            // amount are interchangeable, so only the first of them is tried
+            if load >= self.best || self.loads[..elf].contains(&self.loads[elf]) {
+                continue;
+            }
+            self.loads[elf] = load;
+            self.assigned[item] = elf;
+            let finished = self.run(item + 1);
+            self.loads[elf] -= self.items[item];
+            if !finished {
+                return false;
+            }
+            if self.best == self.lower_bound {
+                return true;
+            }
+        }
+        true
+    }
 }
 
-pub fn part2() {
-    // read input
-    //split input into vector of strings on empty lines
+// what `read_top_k` found in an inventory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub elves: usize,
+    pub total: u64,
+    // (id, calories), the most first
+    pub top: Vec<(usize, u64)>,
+}
 
-    let input = fs::read_to_string("input/day01.in").expect("Error reading file");
-    let input_vec: Vec<&str> = input.split("\n\n").collect();
+// reads the inventory one line at a time from a file or stdin, keeping only the elf
+// being read and the k carrying the most, so the inventory can be far bigger than memory.
+// unlike the puzzle input this can have any number of blank lines between elves
+pub fn read_top_k(mut reader: impl BufRead, k: usize) -> io::Result<Summary> {
+    let mut top = TopK::new(k);
+    let mut summary = Summary {
+        elves: 0,
+        total: 0,
+        top: Vec::new(),
+    };
+    let mut line = String::new();
+    let mut line_number = 0;
+    // calories of the elf being read, `None` between elves
+    let mut current: Option<u64> = None;
+    loop {
+        line.clear();
+        let end = reader.read_line(&mut line)? == 0;
+        line_number += 1;
+        let item = line.trim_start_matches('\u{feff}').trim();
 
-    //top 3 max
-    let mut sums: Vec<i32> = Vec::new();
-    for group in input_vec {
-        //read every line in group and sum
-        let mut sum = 0;
-        let group_vec: Vec<&str> = group.split('\n').collect();
-        //cast to int and sum
-        for line in group_vec {
-            sum += line.parse::<i32>().unwrap();
+        if end || item.is_empty() {
+            if let Some(calories) = current.take() {
+                summary.elves += 1;
+                summary.total += calories;
+                top.push(summary.elves, calories);
+            }
+            if end {
+                break;
+            }
+            continue;
         }
 
-        //insert into sums
-        sums.push(sum);
+        let calories: u64 = item.parse().map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {:?} {}", line_number, item, e),
+            )
+        })?;
+        *current.get_or_insert(0) += calories;
+    }
+    summary.top = top.into_sorted();
+    Ok(summary)
+}
+
+fn parse_input(input: &str) -> CalorieLedger {
+    input.parse().unwrap()
+}
+
+params::params! {
+    pub struct Params {
+        // how many of the elves carrying the most are added up in part 2
+        top: usize = 3,
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    let ledger = parse_input(input);
+    let (_, max) = ledger.max().unwrap();
+    max.to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
+}
+
+pub fn part2_with(input: &str, params: &Params) -> String {
+    let ledger = parse_input(input);
+    let top: u64 = ledger
+        .top_k(params.top)
+        .iter()
+        .map(|(_, calories)| calories)
+        .sum();
+    top.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn ledger_queries() {
+        let ledger = parse_input(EXAMPLE);
+        assert_eq!(ledger.total(), 55000);
+        assert_eq!(ledger.max(), Some((4, 24000)));
+        assert_eq!(ledger.top_k(3), vec![(4, 24000), (3, 11000), (5, 10000)]);
+        assert_eq!(ledger.top_k(10).len(), 5);
+        assert_eq!(ledger.rank_of(1), Some(4));
+        assert_eq!(ledger.rank_of(6), None);
     }
 
-    //sort descending
-    sums.sort_by(|a, b| b.cmp(a));
+    #[test]
+    fn streams_like_the_ledger() {
+        let messy = format!("\u{feff}\n{}\r\n\r\n\n", EXAMPLE.replace('\n', "\r\n"));
+        let summary = read_top_k(messy.as_bytes(), 3).unwrap();
+        let ledger = parse_input(EXAMPLE);
+        assert_eq!(summary.elves, 5);
+        assert_eq!(summary.total, ledger.total());
+        assert_eq!(summary.top, ledger.top_k(3));
 
-    println!("{:?}", sums);
+        let error = read_top_k("1\n\nabc\n".as_bytes(), 1).unwrap_err();
+        assert!(error.to_string().starts_with("line 3:"));
+    }
+
+    #[test]
+    fn plans_better_than_lpt() {
+        // LPT gives 3+2+2 | 3+2, the best is 3+3 | 2+2+2
+        let ledger = parse_input("3\n3\n2\n\n2\n2");
+        let plan = ledger.plan();
+        assert!(plan.optimal);
+        assert_eq!(plan.max_load(), 6);
+        assert_eq!(imbalance(&ledger.totals()), 4);
+        assert_eq!(imbalance(&plan.totals()), 0);
 
-    println!("Max: {}", sums[0] + sums[1] + sums[2]);
+        let plan = parse_input(EXAMPLE).plan();
+        assert_eq!(plan.totals(), vec![11000; 5]);
+    }
+
+    #[test]
+    fn ties_keep_the_lower_id() {
+        let ledger = parse_input("5\n\n7\n\n5\n\n1");
+        assert_eq!(ledger.top_k(2), vec![(2, 7), (1, 5)]);
+        assert_eq!(ledger.rank_of(3), Some(2));
+    }
 }
@@ -1,16 +1,121 @@
-use std::fs;
+use std::{fmt, str::FromStr};
 
-#[derive(Clone, Copy)]
-enum Move {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+// one of the game's moves, by its place in the cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Tie,
+    Win,
+}
+
+// A game where the moves form a cycle and each move beats the (n - 1) / 2 moves before it,
+// so with an odd number of moves every pair of different moves has a winner.
+// Rock paper scissors is the cycle rock, paper, scissors: paper beats the one before it,
+// and rock beats scissors by wrapping around.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub names: Vec<String>,
+    // points for playing each move, in the same order as `names`
+    pub shape_scores: Vec<i32>,
+    // points for losing, tying and winning
+    pub outcome_scores: [i32; 3],
+}
+
+impl Game {
+    // the moves in cycle order, each scoring its place in the cycle like in the puzzle.
+    // the strategy guide writes a move as a letter, so there can't be more than 26
+    pub fn cyclic(names: &[&str]) -> Result<Self, String> {
+        if names.len() < 3 || names.len().is_multiple_of(2) {
+            return Err(format!(
+                "A cyclic game needs an odd number of moves, at least 3, got {}",
+                names.len()
+            ));
+        }
+        if names.len() > 26 {
+            return Err(format!(
+                "A cyclic game can have at most 26 moves, one per letter, got {}",
+                names.len()
+            ));
+        }
+        Ok(Game {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            shape_scores: (1..=names.len() as i32).collect(),
+            outcome_scores: [0, 3, 6],
+        })
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Game::cyclic(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Game::cyclic(&["Rock", "Spock", "Paper", "Lizard", "Scissors"]).unwrap()
+    }
+
+    // the games above for 3 and 5 moves, any other number has moves named by their place
+    pub fn with_moves(n: usize) -> Result<Self, String> {
+        match n {
+            3 => Ok(Game::rock_paper_scissors()),
+            5 => Ok(Game::rock_paper_scissors_lizard_spock()),
+            _ => {
+                let names: Vec<String> = (1..=n).map(|i| format!("Move {}", i)).collect();
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                Game::cyclic(&names)
+            }
+        }
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.names.len()).map(Move)
+    }
+
+    pub fn name(&self, m: Move) -> &str {
+        &self.names[m.0]
+    }
+
+    // how far `ours` is ahead of `theirs` in the cycle decides who wins
+    pub fn outcome(&self, ours: Move, theirs: Move) -> Outcome {
+        let n = self.names.len();
+        match (ours.0 + n - theirs.0) % n {
+            0 => Outcome::Tie,
+            ahead if ahead <= (n - 1) / 2 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+
+    // every move that gets `outcome` against `theirs`
+    pub fn moves_for(&self, theirs: Move, outcome: Outcome) -> impl Iterator<Item = Move> + '_ {
+        self.moves()
+            .filter(move |ours| self.outcome(*ours, theirs) == outcome)
+    }
+
+    // the move that gets `outcome` against `theirs`,
+    // the one scoring the most if there are several
+    pub fn move_for(&self, theirs: Move, outcome: Outcome) -> Move {
+        self.moves_for(theirs, outcome)
+            .max_by_key(|ours| self.shape_scores[ours.0])
+            .unwrap()
+    }
+
+    // what we score for one round
+    pub fn score(&self, ours: Move, theirs: Move) -> i32 {
+        let outcome = self.outcome(ours, theirs);
+        self.shape_scores[ours.0] + self.outcome_scores[outcome as usize]
+    }
 }
 
-enum Result {
-    Win = 6,
-    Tie = 3,
-    Lose = 0,
+// the opponent's moves are A, B, C, ... in cycle order and ours are the last letters
+// of the alphabet, so X, Y, Z for rock paper scissors
+fn letter(s: &str, first: char, count: usize) -> usize {
+    let index = s
+        .chars()
+        .next()
+        .and_then(|c| (c as usize).checked_sub(first as usize))
+        .filter(|index| *index < count);
+    index.unwrap_or_else(|| panic!("Invalid input: {}", s))
 }
 
 // Read
@@ -19,45 +124,28 @@ enum Result {
 // C Y
 // Where A, B, C are opponents moves
 // Y, X, C is our move
-fn read_input(file: &str) -> Vec<(Move, Move)> {
-    let input: String = fs::read_to_string(file).expect("Error reading file");
-    let input_vec: Vec<&str> = input.split('\n').collect();
+fn parse_input(input: &str, game: &Game) -> Vec<(Move, Move)> {
+    let n = game.names.len();
     let mut moves: Vec<(Move, Move)> = Vec::new();
-    for line in input_vec {
+    for line in input.lines() {
         let line_vec: Vec<&str> = line.split(' ').collect();
-        let opponent_move = match line_vec[0] {
-            "A" => Move::Rock,
-            "B" => Move::Paper,
-            "C" => Move::Scissors,
-            _ => panic!("Invalid input"),
-        };
-        let our_move = match line_vec[1] {
-            "X" => Move::Rock,
-            "Y" => Move::Paper,
-            "Z" => Move::Scissors,
-            _ => panic!("Invalid input"),
-        };
+        let opponent_move = Move(letter(line_vec[0], 'A', n));
+        let our_move = Move(letter(line_vec[1], (b'Z' + 1 - n as u8) as char, n));
         moves.push((opponent_move, our_move));
     }
     moves
 }
 
-fn read_input_alt(file: &str) -> Vec<(Move, Result)> {
-    let input: String = fs::read_to_string(file).expect("Error reading file");
-    let input_vec: Vec<&str> = input.split('\n').collect();
-    let mut moves_outcome: Vec<(Move, Result)> = Vec::new();
-    for line in input_vec {
+fn parse_input_alt(input: &str, game: &Game) -> Vec<(Move, Outcome)> {
+    let n = game.names.len();
+    let mut moves_outcome: Vec<(Move, Outcome)> = Vec::new();
+    for line in input.lines() {
         let line_vec: Vec<&str> = line.split(' ').collect();
-        let opponent_move = match line_vec[0] {
-            "A" => Move::Rock,
-            "B" => Move::Paper,
-            "C" => Move::Scissors,
-            _ => panic!("Invalid input"),
-        };
+        let opponent_move = Move(letter(line_vec[0], 'A', n));
         let outcome = match line_vec[1] {
-            "X" => Result::Lose,
-            "Y" => Result::Tie,
-            "Z" => Result::Win,
+            "X" => Outcome::Lose,
+            "Y" => Outcome::Tie,
+            "Z" => Outcome::Win,
             _ => panic!("Invalid input"),
         };
         moves_outcome.push((opponent_move, outcome));
@@ -65,60 +153,550 @@ fn read_input_alt(file: &str) -> Vec<(Move, Result)> {
     moves_outcome
 }
 
-fn calc_score(moves: &Vec<(Move, Move)>) -> i32 {
-    let mut score = 0;
-
-    // let rock be worth 1
-    // let paper be worth 2
-    // let scissors be worth 3
-
-    // our score is our move + 6 if we win, 3 if we tie, 0 if we lose
-    for (opponent_move, our_move) in moves {
-        let outcome = match (opponent_move, our_move) {
-            (Move::Rock, Move::Rock) => Result::Tie,
-            (Move::Rock, Move::Paper) => Result::Win,
-            (Move::Rock, Move::Scissors) => Result::Lose,
-            (Move::Paper, Move::Rock) => Result::Lose,
-            (Move::Paper, Move::Paper) => Result::Tie,
-            (Move::Paper, Move::Scissors) => Result::Win,
-            (Move::Scissors, Move::Rock) => Result::Win,
-            (Move::Scissors, Move::Paper) => Result::Lose,
-            (Move::Scissors, Move::Scissors) => Result::Tie,
-        };
+// our score is what our move is worth plus what the outcome is worth
+pub fn calc_score(game: &Game, moves: &[(Move, Move)]) -> i32 {
+    moves
+        .iter()
+        .map(|(opponent_move, our_move)| game.score(*our_move, *opponent_move))
+        .sum()
+}
+
+// one way of reading what the letters of the second column stand for,
+// X, Y and Z for rock paper scissors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decoding {
+    Moves(Vec<Move>),
+    Outcomes([Outcome; 3]),
+}
+
+// every order of 0..n, in lexicographic order
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    let mut orders = Vec::new();
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut order = vec![first];
+            order.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            orders.push(order);
+        }
+    }
+    orders
+}
+
+impl Decoding {
+    // every order of the game's moves, and with three moves every order of the three outcomes.
+    // a game with more moves has more letters than there are outcomes
+    pub fn all(game: &Game) -> Vec<Decoding> {
+        let n = game.names.len();
+        let orders = permutations(n);
+        let as_moves = orders
+            .iter()
+            .map(|order| Decoding::Moves(order.iter().map(|i| Move(*i)).collect()));
+        let outcomes = [Outcome::Lose, Outcome::Tie, Outcome::Win];
+        let as_outcomes = orders
+            .iter()
+            .filter(|_| n == 3)
+            .map(|order| Decoding::Outcomes(std::array::from_fn(|i| outcomes[order[i]])));
+        as_moves.chain(as_outcomes).collect()
+    }
+
+    // the move we play against `theirs` when the guide says `column`, 0 for the first letter
+    pub fn our_move(&self, game: &Game, theirs: Move, column: usize) -> Move {
+        match self {
+            Decoding::Moves(moves) => moves[column],
+            Decoding::Outcomes(outcomes) => game.move_for(theirs, outcomes[column]),
+        }
+    }
+
+    // what each letter means, e.g. ["Rock", "Paper", "Scissors"] for X, Y and Z
+    pub fn meanings(&self, game: &Game) -> Vec<String> {
+        match self {
+            Decoding::Moves(moves) => moves.iter().map(|m| game.name(*m).to_string()).collect(),
+            Decoding::Outcomes(outcomes) => outcomes.iter().map(|o| format!("{:?}", o)).collect(),
+        }
+    }
+}
+
+// the letters of the second column, the last letters of the alphabet
+pub fn our_letters(game: &Game) -> Vec<char> {
+    let n = game.names.len();
+    (0..n)
+        .map(|i| (b'Z' + 1 - n as u8 + i as u8) as char)
+        .collect()
+}
+
+// how many times each kind of round, like "A X", is in the guide.
+// the kinds are A X, A Y, A Z, B X, ... in that order
+pub fn round_counts(input: &str, game: &Game) -> Vec<usize> {
+    let n = game.names.len();
+    let mut counts = vec![0; n * n];
+    for (theirs, column) in parse_input(input, game) {
+        counts[theirs.0 * n + column.0] += 1;
+    }
+    counts
+}
+
+// the guide's score under one decoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    pub decoding: Decoding,
+    // what one round of each kind scores, in the same order as `round_counts`
+    pub round_scores: Vec<i32>,
+    pub total: i32,
+}
+
+// every decoding of the guide, the one scoring the most first
+pub fn analyse(input: &str, game: &Game) -> Vec<Interpretation> {
+    let n = game.names.len();
+    let counts = round_counts(input, game);
+    let mut interpretations: Vec<Interpretation> = Decoding::all(game)
+        .into_iter()
+        .map(|decoding| {
+            let round_scores: Vec<i32> = (0..n * n)
+                .map(|kind| {
+                    let theirs = Move(kind / n);
+                    game.score(decoding.our_move(game, theirs, kind % n), theirs)
+                })
+                .collect();
+            let total = round_scores
+                .iter()
+                .zip(&counts)
+                .map(|(score, count)| score * *count as i32)
+                .sum();
+            Interpretation {
+                decoding,
+                round_scores,
+                total,
+            }
+        })
+        .collect();
+    interpretations.sort_by_key(|interpretation| std::cmp::Reverse(interpretation.total));
+    interpretations
+}
+
+// xorshift, seeded so that a tournament can be played again with the same results
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves zero, so mix the seed with a constant first
+        Rng((seed ^ 0x2545_f491_4f6c_dd1d).max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // a number in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
 
-        score += outcome as i32 + *our_move as i32;
-        println!("{}", score);
+// A way of playing a match. `history` is every round so far as (their move, our move),
+// the same order `calc_score` takes them in.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    fn next_move(&self, game: &Game, history: &[(Move, Move)], rng: &mut Rng) -> Move;
+}
+
+// plays the moves of a strategy guide in order, starting over when it runs out
+pub struct FixedGuide {
+    pub name: String,
+    pub moves: Vec<Move>,
+}
+
+impl FixedGuide {
+    // our moves from the puzzle input, reading X, Y and Z as moves like part 1.
+    // a guide without any rounds has no move to start over with
+    pub fn from_input(name: &str, input: &str, game: &Game) -> Result<Self, String> {
+        let moves: Vec<Move> = parse_input(input, game)
+            .into_iter()
+            .map(|(_, our_move)| our_move)
+            .collect();
+        if moves.is_empty() {
+            return Err(format!("The strategy guide for {} has no rounds", name));
+        }
+        Ok(FixedGuide {
+            name: name.to_string(),
+            moves,
+        })
     }
-    score
 }
 
-pub fn part1() {
-    let moves = read_input("input/day02.in");
-    let score = calc_score(&moves);
-    println!("Part 1: {}", score);
+impl Strategy for FixedGuide {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn next_move(&self, _: &Game, history: &[(Move, Move)], _: &mut Rng) -> Move {
+        self.moves[history.len() % self.moves.len()]
+    }
 }
 
-pub fn part2() {
-    let move_outcome = read_input_alt("input/day02.in");
+// any move, all equally likely
+pub struct Random;
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn next_move(&self, game: &Game, _: &[(Move, Move)], rng: &mut Rng) -> Move {
+        Move(rng.below(game.names.len()))
+    }
+}
+
+// beats the move the opponent played the most so far, random until it has seen one
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn next_move(&self, game: &Game, history: &[(Move, Move)], rng: &mut Rng) -> Move {
+        let mut counts = vec![0; game.names.len()];
+        for (theirs, _) in history {
+            counts[theirs.0] += 1;
+        }
+        // the first of the most played wins a tie
+        let most = (0..counts.len()).rev().max_by_key(|m| counts[*m]);
+        match most {
+            Some(m) if counts[m] > 0 => game.move_for(Move(m), Outcome::Win),
+            _ => Move(rng.below(game.names.len())),
+        }
+    }
+}
+
+// beats whatever the opponent played last round, random in the first round
+pub struct BeatLast;
+
+impl Strategy for BeatLast {
+    fn name(&self) -> String {
+        "beat last move".to_string()
+    }
+
+    fn next_move(&self, game: &Game, history: &[(Move, Move)], rng: &mut Rng) -> Move {
+        match history.last() {
+            Some((theirs, _)) => game.move_for(*theirs, Outcome::Win),
+            None => Move(rng.below(game.names.len())),
+        }
+    }
+}
+
+// plays `rounds` rounds and returns what each side scored
+pub fn play_match(
+    game: &Game,
+    first: &dyn Strategy,
+    second: &dyn Strategy,
+    rounds: usize,
+    rng: &mut Rng,
+) -> (i32, i32) {
+    // each side sees the rounds from its own side, as (their move, our move)
+    let mut first_history: Vec<(Move, Move)> = Vec::with_capacity(rounds);
+    let mut second_history: Vec<(Move, Move)> = Vec::with_capacity(rounds);
+    for _ in 0..rounds {
+        let a = first.next_move(game, &first_history, rng);
+        let b = second.next_move(game, &second_history, rng);
+        first_history.push((b, a));
+        second_history.push((a, b));
+    }
+    (
+        calc_score(game, &first_history),
+        calc_score(game, &second_history),
+    )
+}
+
+// how a strategy did over all its matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub wins: usize,
+    pub ties: usize,
+    pub losses: usize,
+    // all the points scored in all the rounds
+    pub score: i64,
+}
+
+impl Standing {
+    // 3 for winning a match, 1 for a tie
+    pub fn points(&self) -> usize {
+        self.wins * 3 + self.ties
+    }
+}
+
+// every strategy plays one match of `rounds` rounds against each of the others,
+// the match going to whoever scored more. returns the leaderboard, best first
+pub fn tournament(
+    game: &Game,
+    strategies: &[Box<dyn Strategy>],
+    rounds: usize,
+    seed: u64,
+) -> Vec<Standing> {
+    let mut rng = Rng::new(seed);
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|strategy| Standing {
+            name: strategy.name(),
+            wins: 0,
+            ties: 0,
+            losses: 0,
+            score: 0,
+        })
+        .collect();
+    for i in 0..strategies.len() {
+        for j in i + 1..strategies.len() {
+            let (a, b) = play_match(game, &*strategies[i], &*strategies[j], rounds, &mut rng);
+            standings[i].score += a as i64;
+            standings[j].score += b as i64;
+            match a.cmp(&b) {
+                std::cmp::Ordering::Greater => {
+                    standings[i].wins += 1;
+                    standings[j].losses += 1;
+                }
+                std::cmp::Ordering::Less => {
+                    standings[i].losses += 1;
+                    standings[j].wins += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    standings[i].ties += 1;
+                    standings[j].ties += 1;
+                }
+            }
+        }
+    }
+    standings.sort_by_key(|standing| std::cmp::Reverse((standing.points(), standing.score)));
+    standings
+}
+
+// points for playing each move, in cycle order and separated by commas like 1,2,3.
+// `cycle` scores each move its place in the cycle, like the puzzle
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapeScores(pub Option<Vec<i32>>);
+
+impl FromStr for ShapeScores {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "cycle" {
+            return Ok(ShapeScores(None));
+        }
+        let scores = s
+            .split(',')
+            .map(|score| {
+                score
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid score {}", score))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(ShapeScores(Some(scores)))
+    }
+}
+
+impl fmt::Display for ShapeScores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            None => write!(f, "cycle"),
+            Some(scores) => {
+                let scores: Vec<String> = scores.iter().map(i32::to_string).collect();
+                write!(f, "{}", scores.join(","))
+            }
+        }
+    }
+}
+
+params::params! {
+    pub struct Params {
+        lose: i32 = 0,
+        tie: i32 = 3,
+        win: i32 = 6,
+        shape_scores: ShapeScores = ShapeScores(None),
+        // how many moves the game has, an odd number up to 26. 3 is rock paper scissors
+        // with A..C and X..Z, 5 adds lizard and spock with A..E and V..Z
+        moves: usize = 3,
+    }
+}
+
+impl Params {
+    // there has to be a shape score for every move of the game
+    pub fn game(&self) -> Result<Game, String> {
+        let mut game = Game::with_moves(self.moves)?;
+        game.outcome_scores = [self.lose, self.tie, self.win];
+        if let Some(scores) = &self.shape_scores.0 {
+            if scores.len() != game.names.len() {
+                return Err(format!(
+                    "shape_scores needs a score for each of the {} moves, got {}",
+                    game.names.len(),
+                    self.shape_scores
+                ));
+            }
+            game.shape_scores = scores.clone();
+        }
+        Ok(game)
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    try_part1_with(input, &Params::default()).unwrap()
+}
+
+pub fn try_part1_with(input: &str, params: &Params) -> Result<String, String> {
+    let game = params.game()?;
+    let moves = parse_input(input, &game);
+    let score = calc_score(&game, &moves);
+    Ok(score.to_string())
+}
+
+pub fn part2(input: &str) -> String {
+    try_part2_with(input, &Params::default()).unwrap()
+}
+
+pub fn try_part2_with(input: &str, params: &Params) -> Result<String, String> {
+    let game = params.game()?;
+    let move_outcome = parse_input_alt(input, &game);
 
     // determine our move based on the outcome
-    let mut moves = Vec::new();
-    for (opponent_move, outcome) in move_outcome {
-        let our_move = match (opponent_move, outcome) {
-            (Move::Rock, Result::Win) => Move::Paper,
-            (Move::Rock, Result::Tie) => Move::Rock,
-            (Move::Rock, Result::Lose) => Move::Scissors,
-            (Move::Paper, Result::Win) => Move::Scissors,
-            (Move::Paper, Result::Tie) => Move::Paper,
-            (Move::Paper, Result::Lose) => Move::Rock,
-            (Move::Scissors, Result::Win) => Move::Rock,
-            (Move::Scissors, Result::Tie) => Move::Scissors,
-            (Move::Scissors, Result::Lose) => Move::Paper,
+    let moves: Vec<(Move, Move)> = move_outcome
+        .into_iter()
+        .map(|(opponent_move, outcome)| (opponent_move, game.move_for(opponent_move, outcome)))
+        .collect();
+
+    let score = calc_score(&game, &moves);
+
+    Ok(score.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rock_paper_scissors() {
+        let game = Game::rock_paper_scissors();
+        let (rock, paper, scissors) = (Move(0), Move(1), Move(2));
+        assert_eq!(game.outcome(paper, rock), Outcome::Win);
+        assert_eq!(game.outcome(rock, scissors), Outcome::Win);
+        assert_eq!(game.outcome(scissors, rock), Outcome::Lose);
+        assert_eq!(game.move_for(rock, Outcome::Lose), scissors);
+        assert_eq!(part1("A Y\nB X\nC Z"), "15");
+        assert_eq!(part2("A Y\nB X\nC Z"), "12");
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        for theirs in game.moves() {
+            let wins = game.moves_for(theirs, Outcome::Win).count();
+            let losses = game.moves_for(theirs, Outcome::Lose).count();
+            assert_eq!((wins, losses), (2, 2));
+        }
+        let (rock, lizard, spock) = (Move(0), Move(3), Move(1));
+        assert_eq!(game.outcome(lizard, spock), Outcome::Win);
+        assert_eq!(game.outcome(rock, lizard), Outcome::Win);
+        assert!(Game::cyclic(&["a", "b", "c", "d"]).is_err());
+    }
+
+    #[test]
+    fn any_number_of_moves() {
+        let game = Game::with_moves(7).unwrap();
+        assert_eq!(game.name(Move(6)), "Move 7");
+        assert_eq!(our_letters(&game), vec!['T', 'U', 'V', 'W', 'X', 'Y', 'Z']);
+        let game = Game::with_moves(25).unwrap();
+        assert_eq!(our_letters(&game)[0], 'B');
+        assert!(Game::with_moves(27).is_err());
+
+        // our T is move 1, which beats their G, move 7, by wrapping around: 1 + 6 points
+        let params = Params {
+            moves: 7,
+            ..Params::default()
         };
-        moves.push((opponent_move, our_move));
+        assert_eq!(try_part1_with("G T", &params).unwrap(), "7");
+        // a tie with move 1 is 1 + 3, and move 2 beats move 1 for 2 + 6
+        assert_eq!(try_part1_with("A T\nA U", &params).unwrap(), "12");
+        let params = Params {
+            moves: 27,
+            ..Params::default()
+        };
+        assert!(try_part1_with("A Z", &params).is_err());
     }
 
-    let score = calc_score(&moves);
+    #[test]
+    fn every_decoding() {
+        let game = Game::rock_paper_scissors();
+        let interpretations = analyse("A Y\nB X\nC Z", &game);
+        assert_eq!(interpretations.len(), 12);
+        let total = |decoding| {
+            let found = interpretations.iter().find(|i| i.decoding == decoding);
+            found.unwrap().total
+        };
+        let puzzle = Decoding::Moves(vec![Move(0), Move(1), Move(2)]);
+        let alt = Decoding::Outcomes([Outcome::Lose, Outcome::Tie, Outcome::Win]);
+        assert_eq!((total(puzzle), total(alt)), (15, 12));
+        assert!(interpretations.windows(2).all(|w| w[0].total >= w[1].total));
 
-    println!("Part 2: {}", score);
+        // five letters can't be read as three outcomes, so only the 120 orders of the moves
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let interpretations = analyse("A V\nE Z\nC X", &game);
+        assert_eq!(interpretations.len(), 120);
+        assert_eq!(interpretations[0].round_scores.len(), 25);
+        let identity = Decoding::Moves(game.moves().collect());
+        let found = interpretations.iter().find(|i| i.decoding == identity);
+        let params = Params {
+            moves: 5,
+            ..Params::default()
+        };
+        let part1 = try_part1_with("A V\nE Z\nC X", &params).unwrap();
+        assert_eq!(found.unwrap().total.to_string(), part1);
+    }
+
+    #[test]
+    fn shape_scores() {
+        let scores: ShapeScores = "10, 20,30".parse().unwrap();
+        assert_eq!(scores, ShapeScores(Some(vec![10, 20, 30])));
+        assert_eq!(scores.to_string(), "10,20,30");
+        assert_eq!("cycle".parse(), Ok(ShapeScores(None)));
+        assert!("1,x".parse::<ShapeScores>().is_err());
+
+        // rock 10 + tie 3, paper 20 + loss 0, scissors 30 + tie 3
+        let params = Params {
+            shape_scores: scores,
+            ..Params::default()
+        };
+        assert_eq!(try_part1_with("A X\nC Y\nC Z", &params).unwrap(), "66");
+        let params = Params { moves: 5, ..params };
+        assert!(try_part1_with("A X", &params).is_err());
+    }
+
+    #[test]
+    fn tournament_is_seeded() {
+        let game = Game::rock_paper_scissors();
+        let strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(FixedGuide::from_input("rock", "A X", &game).unwrap()),
+            Box::new(BeatLast),
+            Box::new(FrequencyCounter),
+            Box::new(Random),
+        ];
+        let standings = tournament(&game, &strategies, 100, 7);
+        assert_eq!(standings, tournament(&game, &strategies, 100, 7));
+        // always playing rock loses to both strategies that learn from it
+        let rock = standings.iter().find(|s| s.name == "rock").unwrap();
+        assert!(rock.losses >= 2);
+        let games: usize = standings.iter().map(|s| s.wins + s.ties + s.losses).sum();
+        assert_eq!(games, 12);
+    }
+
+    #[test]
+    fn empty_guide() {
+        let game = Game::rock_paper_scissors();
+        assert!(FixedGuide::from_input("empty", "", &game).is_err());
+    }
 }
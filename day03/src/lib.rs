@@ -1,64 +1,484 @@
-use std::fs;
-
-fn read_input(file: &str) -> Vec<String> {
-    let input: String = fs::read_to_string(file).expect("Error reading file");
-    input.split('\n').map(|s| s.to_string()).collect()
-}
-
-pub fn part1() {
-    let bags = read_input("input/day03.in");
-    let mut sum = 0;
-    for bag in bags {
-        let len = bag.len();
-        let first_half = &bag[0..len / 2];
-        let second_half = &bag[len / 2..len];
-
-        //find letter appears in both halves
-        let mut common_char: char = ' ';
-        for c in first_half.chars() {
-            if second_half.contains(c) {
-                common_char = c;
-                break;
+use std::{ops::BitAnd, str::FromStr};
+
+// A set of item types, bit p is set for the item with priority p.
+// Lowercase item types a through z have priorities 1 through 26.
+// Uppercase item types A through Z have priorities 27 through 52.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    // every item type there is
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    pub fn priority(item: char) -> Option<u32> {
+        match item {
+            'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+            _ => None,
+        }
+    }
+
+    // the item with priority `priority`, which has to be 1 to 52
+    pub fn item(priority: u32) -> char {
+        match priority {
+            1..=26 => (b'a' + priority as u8 - 1) as char,
+            27..=52 => (b'A' + priority as u8 - 27) as char,
+            _ => panic!("No item has priority {}", priority),
+        }
+    }
+
+    // false if `item` isn't an item type
+    pub fn insert(&mut self, item: char) -> bool {
+        match ItemSet::priority(item) {
+            Some(priority) => {
+                self.0 |= 1 << priority;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        ItemSet::priority(item).is_some_and(|priority| self.0 & (1 << priority) != 0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn priority_sum(&self) -> u32 {
+        self.priorities().sum()
+    }
+
+    // the priorities of the items in the set, lowest first
+    pub fn priorities(&self) -> impl Iterator<Item = u32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let priority = bits.trailing_zeros();
+            bits &= bits - 1;
+            Some(priority)
+        })
+    }
+
+    // the items in the set, a to z and then A to Z
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        self.priorities().map(ItemSet::item)
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        self.intersection(other)
+    }
+}
+
+impl FromStr for ItemSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = ItemSet::default();
+        for item in s.chars() {
+            if !set.insert(item) {
+                return Err(format!("Invalid item {:?} in {}", item, s));
+            }
+        }
+        Ok(set)
+    }
+}
+
+fn parse_input(input: &str) -> Vec<String> {
+    input.lines().map(|s| s.to_string()).collect()
+}
+
+fn item_set(items: &str) -> ItemSet {
+    items
+        .parse()
+        .unwrap_or_else(|e| panic!("Invalid input: {}", e))
+}
+
+// the items that are in both compartments of each bag
+pub fn misplaced(input: &str) -> Vec<ItemSet> {
+    parse_input(input)
+        .iter()
+        .map(|bag| {
+            let (first_half, second_half) = bag.split_at(bag.len() / 2);
+            item_set(first_half) & item_set(second_half)
+        })
+        .collect()
+}
+
+// the items that every bag in a group of `group_size` bags has
+pub fn common_items(input: &str, group_size: usize) -> Result<Vec<ItemSet>, String> {
+    let bags = parse_input(input);
+    if group_size == 0 || !bags.len().is_multiple_of(group_size) {
+        return Err(format!(
+            "group_size must divide the number of rucksacks, {} bags don't split into groups of {}",
+            bags.len(),
+            group_size
+        ));
+    }
+    Ok(bags
+        .chunks(group_size)
+        .map(|group| {
+            group
+                .iter()
+                .fold(ItemSet::ALL, |common, bag| common & item_set(bag))
+        })
+        .collect())
+}
+
+// Splits bags given in any order into groups of three where the three bags have exactly
+// one item in common, their badge. Returns the groups as indexes into `bags`, each group
+// in increasing order, or `None` if the bags can't be split that way.
+//
+// There's usually more than one way to split them, a puzzle input has hundreds of
+// thousands of triples with one badge, and any of the ways is returned. Bags that are
+// already in order, every three in a row sharing one item, keep that grouping.
+//
+// This is an exact cover: every triple that fits is a candidate and each bag has to be
+// in exactly one of the chosen ones. Like Knuth's algorithm X the search always groups
+// the bag with the fewest candidates left next, and backs off as soon as a bag has none.
+pub fn badge_groups(bags: &[ItemSet]) -> Option<Vec<[usize; 3]>> {
+    if !bags.len().is_multiple_of(3) {
+        return None;
+    }
+    let in_order = bags
+        .chunks(3)
+        .all(|group| (group[0] & group[1] & group[2]).len() == 1);
+    if in_order {
+        return Some(
+            (0..bags.len() / 3)
+                .map(|g| [3 * g, 3 * g + 1, 3 * g + 2])
+                .collect(),
+        );
+    }
+
+    let mut search = GroupSearch::new(bags);
+    search.run().then_some(search.groups)
+}
+
+struct GroupSearch {
+    // every triple of bags that shares exactly one item
+    triples: Vec<[usize; 3]>,
+    // the triples each bag is in
+    by_bag: Vec<Vec<usize>>,
+    // how many of a triple's bags are grouped, it's a candidate while this is 0
+    blocked: Vec<u8>,
+    // how many candidates each bag has left
+    candidates: Vec<usize>,
+    grouped: Vec<bool>,
+    groups: Vec<[usize; 3]>,
+}
+
+impl GroupSearch {
+    fn new(bags: &[ItemSet]) -> Self {
+        let mut triples = Vec::new();
+        for i in 0..bags.len() {
+            for j in i + 1..bags.len() {
+                let common = bags[i] & bags[j];
+                if common.is_empty() {
+                    continue;
+                }
+                for (k, bag) in bags.iter().enumerate().skip(j + 1) {
+                    if (common & *bag).len() == 1 {
+                        triples.push([i, j, k]);
+                    }
+                }
+            }
+        }
+        let mut by_bag = vec![Vec::new(); bags.len()];
+        for (t, triple) in triples.iter().enumerate() {
+            for bag in triple {
+                by_bag[*bag].push(t);
+            }
+        }
+        GroupSearch {
+            blocked: vec![0; triples.len()],
+            candidates: by_bag.iter().map(Vec::len).collect(),
+            grouped: vec![false; bags.len()],
+            groups: Vec::with_capacity(bags.len() / 3),
+            triples,
+            by_bag,
+        }
+    }
+
+    // takes the triple's bags out of the search, and every other triple they're in with them
+    fn group(&mut self, triple: [usize; 3]) {
+        for bag in triple {
+            self.grouped[bag] = true;
+            for &t in &self.by_bag[bag] {
+                if self.blocked[t] == 0 {
+                    for other in self.triples[t] {
+                        self.candidates[other] -= 1;
+                    }
+                }
+                self.blocked[t] += 1;
             }
         }
+        self.groups.push(triple);
+    }
 
-        // Lowercase item types a through z have priorities 1 through 26.
-        // Uppercase item types A through Z have priorities 27 through 52.
-        sum += match common_char {
-            'a'..='z' => common_char as u32 - 'a' as u32 + 1,
-            'A'..='Z' => common_char as u32 - 'A' as u32 + 27,
-            _ => panic!("Invalid input"),
+    // `group` backwards
+    fn ungroup(&mut self, triple: [usize; 3]) {
+        self.groups.pop();
+        for bag in triple.into_iter().rev() {
+            for &t in &self.by_bag[bag] {
+                self.blocked[t] -= 1;
+                if self.blocked[t] == 0 {
+                    for other in self.triples[t] {
+                        self.candidates[other] += 1;
+                    }
+                }
+            }
+            self.grouped[bag] = false;
+        }
+    }
+
+    fn run(&mut self) -> bool {
+        let next = (0..self.grouped.len())
+            .filter(|bag| !self.grouped[*bag])
+            .min_by_key(|bag| self.candidates[*bag]);
+        let Some(bag) = next else {
+            return true;
         };
+        let tries: Vec<[usize; 3]> = self.by_bag[bag]
+            .iter()
+            .filter(|t| self.blocked[**t] == 0)
+            .map(|t| self.triples[*t])
+            .collect();
+        for triple in tries {
+            self.group(triple);
+            if self.run() {
+                return true;
+            }
+            self.ungroup(triple);
+        }
+        false
     }
+}
+
+// part 2 for bags in any order: the sum of the badges of one way of grouping them,
+// see `badge_groups` for which one. Different orders of the same bags can give
+// different sums, only bags in the puzzle's order are sure to give part 2's answer.
+pub fn try_part2_shuffled(input: &str) -> Result<String, String> {
+    let bags: Vec<ItemSet> = parse_input(input).iter().map(|bag| item_set(bag)).collect();
+    let groups = badge_groups(&bags).ok_or_else(|| {
+        format!(
+            "The {} bags can't be split into groups with one badge each",
+            bags.len()
+        )
+    })?;
+    let sum: u32 = groups
+        .iter()
+        .map(|[i, j, k]| (bags[*i] & bags[*j] & bags[*k]).priority_sum())
+        .sum();
+    Ok(sum.to_string())
+}
+
+pub fn part2_shuffled(input: &str) -> String {
+    try_part2_shuffled(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+params::params! {
+    pub struct Params {
+        // how many elves are in a group in part 2
+        group_size: usize = 3,
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    let sum: u32 = misplaced(input).iter().map(ItemSet::priority_sum).sum();
+    sum.to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
+}
 
-    println!("{}", sum);
+pub fn part2_with(input: &str, params: &Params) -> String {
+    try_part2_with(input, params).unwrap_or_else(|e| panic!("{}", e))
 }
 
-pub fn part2() {
-    let bags = read_input("input/day03.in");
-    let mut sum = 0;
-    //iterate over 3 bags at a time
-    for i in (0..bags.len()).step_by(3) {
-        let bag1 = &bags[i];
-        let bag2 = &bags[i + 1];
-        let bag3 = &bags[i + 2];
+// every item a whole group has counts, not just the first one found
+pub fn try_part2_with(input: &str, params: &Params) -> Result<String, String> {
+    let sum: u32 = common_items(input, params.group_size)?
+        .iter()
+        .map(ItemSet::priority_sum)
+        .sum();
+    Ok(sum.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn item_sets() {
+        let set: ItemSet = "abcXYZ".parse().unwrap();
+        let other: ItemSet = "cdZ".parse().unwrap();
+        assert_eq!((set & other).iter().collect::<String>(), "cZ");
+        assert_eq!((set & other).priority_sum(), 3 + 52);
+        assert_eq!(set.len(), 6);
+        assert!(set.contains('X') && !set.contains('x'));
+        assert_eq!(ItemSet::ALL.len(), 52);
+        assert!("ab1".parse::<ItemSet>().is_err());
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE), "157");
+        assert_eq!(part2(EXAMPLE), "70");
+        // the whole example as one group has nothing in common, as pairs a few items
+        let params = Params { group_size: 6 };
+        assert_eq!(part2_with(EXAMPLE, &params), "0");
+        let pairs = common_items(EXAMPLE, 2).unwrap();
+        assert_eq!(pairs[0].iter().collect::<String>(), "frsFM");
+        // 6 bags don't split into groups of 4, or of 0
+        assert!(try_part2_with(EXAMPLE, &Params { group_size: 4 }).is_err());
+        assert!(try_part2_with(EXAMPLE, &Params { group_size: 0 }).is_err());
+    }
 
-        //find letter appears in all 3 bags
-        let mut common_char: char = ' ';
-        for c in bag1.chars() {
-            if bag2.contains(c) && bag3.contains(c) {
-                common_char = c;
-                break;
+    #[test]
+    fn shuffled_groups() {
+        let bags: Vec<ItemSet> = EXAMPLE.lines().rev().map(item_set).collect();
+        let groups = badge_groups(&bags).unwrap();
+        assert_eq!(groups, vec![[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(part2_shuffled(EXAMPLE), "70");
+
+        // a and b share two items, so no group has exactly one badge
+        let bags: Vec<ItemSet> = ["ab", "ab", "ab"].map(item_set).to_vec();
+        assert_eq!(badge_groups(&bags), None);
+        assert_eq!(badge_groups(&bags[..2]), None);
+        assert!(try_part2_shuffled("ab\nab\nab").is_err());
+    }
+
+    // 40 groups of bags with 12 random items each, and one badge per group
+    fn random_bags(seed: u64) -> Vec<ItemSet> {
+        let mut seed = seed;
+        let mut next = move |n: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n) as u32 + 1
+        };
+        let mut bags = Vec::new();
+        for _ in 0..40 {
+            let badge = ItemSet::item(next(52));
+            let mut group = [ItemSet::default(); 3];
+            for bag in &mut group {
+                bag.insert(badge);
+                for _ in 0..12 {
+                    bag.insert(ItemSet::item(next(52)));
+                }
             }
+            // anything else the whole group has comes out of the last bag
+            let common = group[0] & group[1] & group[2];
+            let mut last = ItemSet::default();
+            for item in group[2].iter() {
+                if item == badge || !common.contains(item) {
+                    last.insert(item);
+                }
+            }
+            group[2] = last;
+            bags.extend(group);
         }
+        bags
+    }
 
-        // Lowercase item types a through z have priorities 1 through 26.
-        // Uppercase item types A through Z have priorities 27 through 52.
-        sum += match common_char {
-            'a'..='z' => common_char as u32 - 'a' as u32 + 1,
-            'A'..='Z' => common_char as u32 - 'A' as u32 + 27,
-            _ => panic!("Invalid input"),
+    // the same bags in an order picked by `seed`
+    fn shuffle(bags: &[ItemSet], seed: u64) -> Vec<ItemSet> {
+        let mut order: Vec<usize> = (0..bags.len()).collect();
+        let mut seed = seed;
+        for i in (1..order.len()).rev() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            order.swap(i, (seed % (i as u64 + 1)) as usize);
+        }
+        order.iter().map(|i| bags[*i]).collect()
+    }
+
+    fn to_input(bags: &[ItemSet]) -> String {
+        let lines: Vec<String> = bags.iter().map(|bag| bag.iter().collect()).collect();
+        lines.join("\n")
+    }
+
+    #[test]
+    fn shuffled_groups_of_many_bags() {
+        let bags = random_bags(0x2545_f491_4f6c_dd1d);
+        assert_eq!(part2_shuffled(&to_input(&bags)), part2(&to_input(&bags)));
+
+        // shuffled, the groups found needn't be the ones made, but they have to fit
+        let shuffled = shuffle(&bags, 2022);
+        let groups = badge_groups(&shuffled).unwrap();
+        let mut seen = vec![false; shuffled.len()];
+        for [i, j, k] in &groups {
+            assert_eq!((shuffled[*i] & shuffled[*j] & shuffled[*k]).len(), 1);
+            for bag in [i, j, k] {
+                assert!(!seen[*bag]);
+                seen[*bag] = true;
+            }
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+    // 20 groups with a different badge each, and the other 32 items in two random bags
+    // each. an item has to be in all three bags of a group, so only the badges can be,
+    // and the groups made are the only way to split the bags
+    fn bags_with_one_grouping(seed: u64) -> Vec<ItemSet> {
+        let mut seed = seed;
+        let mut next = move |n: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n) as usize
         };
+        let mut bags = vec![ItemSet::default(); 60];
+        for (i, bag) in bags.iter_mut().enumerate() {
+            bag.insert(ItemSet::item(i as u32 / 3 + 1));
+        }
+        for priority in 21..=52 {
+            for _ in 0..2 {
+                bags[next(60)].insert(ItemSet::item(priority));
+            }
+        }
+        bags
+    }
+
+    #[test]
+    fn shuffled_sum_matches_part2() {
+        // in order the shortcut finds the groups, shuffled the search has to
+        let bags = bags_with_one_grouping(0x9e37_79b9_7f4a_7c15);
+        let shuffled = shuffle(&bags, 2022);
+        let in_order = shuffled
+            .chunks(3)
+            .all(|group| (group[0] & group[1] & group[2]).len() == 1);
+        assert!(
+            !in_order,
+            "the shuffle has to leave the search something to do"
+        );
+        assert_eq!(
+            part2_shuffled(&to_input(&shuffled)),
+            part2(&to_input(&bags))
+        );
     }
-    println!("{}", sum);
 }
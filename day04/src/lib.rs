@@ -1,62 +1,220 @@
-use std::fs;
+use std::str::FromStr;
 
-struct Range {
-    start: u32,
-    end: u32,
+// the sections start to end, both included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: u32,
+    pub end: u32,
 }
 
-fn read_input(file: &str) -> Vec<(Range, Range)> {
-    let input: String = fs::read_to_string(file).expect("Error reading file");
+// The 13 ways two intervals can relate to each other, from James Allen's interval algebra,
+// named for how the first relates to the second.
+// A section is treated as the time from its start to the start of the next one, so
+// 1-2 meets 3-4 since nothing is in between, while 1-2 overlaps 2-3 since both have 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    Before,
+    Meets,
+    Overlaps,
+    Starts,
+    During,
+    Finishes,
+    Equals,
+    FinishedBy,
+    Contains,
+    StartedBy,
+    OverlappedBy,
+    MetBy,
+    After,
+}
+
+impl Relation {
+    pub const ALL: [Relation; 13] = [
+        Relation::Before,
+        Relation::Meets,
+        Relation::Overlaps,
+        Relation::Starts,
+        Relation::During,
+        Relation::Finishes,
+        Relation::Equals,
+        Relation::FinishedBy,
+        Relation::Contains,
+        Relation::StartedBy,
+        Relation::OverlappedBy,
+        Relation::MetBy,
+        Relation::After,
+    ];
+
+    // how the second relates to the first
+    pub fn inverse(self) -> Relation {
+        Relation::ALL[12 - self as usize]
+    }
+
+    // one of them has every section the other has
+    pub fn is_containment(self) -> bool {
+        !self.is_disjoint() && !matches!(self, Relation::Overlaps | Relation::OverlappedBy)
+    }
+
+    // they have no section in common
+    pub fn is_disjoint(self) -> bool {
+        matches!(
+            self,
+            Relation::Before | Relation::Meets | Relation::MetBy | Relation::After
+        )
+    }
+}
+
+impl Range {
+    pub fn relation(&self, other: &Range) -> Relation {
+        use std::cmp::Ordering::*;
+        // compare where the sections end, which is the start of the next section.
+        // in u64, since the section after u32::MAX doesn't fit in a u32
+        let (start, end) = (self.start as u64, self.end as u64 + 1);
+        let (other_start, other_end) = (other.start as u64, other.end as u64 + 1);
+        if end < other_start {
+            return Relation::Before;
+        }
+        if end == other_start {
+            return Relation::Meets;
+        }
+        if other_end < start {
+            return Relation::After;
+        }
+        if other_end == start {
+            return Relation::MetBy;
+        }
+        match (start.cmp(&other_start), end.cmp(&other_end)) {
+            (Less, Less) => Relation::Overlaps,
+            (Less, Equal) => Relation::FinishedBy,
+            (Less, Greater) => Relation::Contains,
+            (Equal, Less) => Relation::Starts,
+            (Equal, Equal) => Relation::Equals,
+            (Equal, Greater) => Relation::StartedBy,
+            (Greater, Less) => Relation::During,
+            (Greater, Equal) => Relation::Finishes,
+            (Greater, Greater) => Relation::OverlappedBy,
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = String;
+
+    // "2-4"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Expected start-end, got {}", s))?;
+        let parse = |n: &str| {
+            n.parse::<u32>()
+                .map_err(|e| format!("Invalid section {:?} in {}: {}", n, s, e))
+        };
+        let range = Range {
+            start: parse(start)?,
+            end: parse(end)?,
+        };
+        if range.start > range.end {
+            return Err(format!("The range {} ends before it starts", s));
+        }
+        Ok(range)
+    }
+}
 
+fn parse_input(input: &str) -> Vec<(Range, Range)> {
+    let range = |s: &str| {
+        s.parse::<Range>()
+            .unwrap_or_else(|e| panic!("Invalid input: {}", e))
+    };
     let mut ranges = Vec::new();
     for line in input.lines() {
         let mut parts = line.split(',');
-        let range1 = parts.next().unwrap();
-        let range2 = parts.next().unwrap();
-        let mut parts = range1.split('-');
-        let start1 = parts.next().unwrap().parse::<u32>().unwrap();
-        let end1 = parts.next().unwrap().parse::<u32>().unwrap();
-        let mut parts = range2.split('-');
-        let start2 = parts.next().unwrap().parse::<u32>().unwrap();
-        let end2 = parts.next().unwrap().parse::<u32>().unwrap();
-        ranges.push((
-            Range {
-                start: start1,
-                end: end1,
-            },
-            Range {
-                start: start2,
-                end: end2,
-            },
-        ));
+        let range1 = range(parts.next().unwrap());
+        let range2 = range(parts.next().unwrap());
+        ranges.push((range1, range2));
     }
     ranges
 }
 
-pub fn part1() {
-    // count the number of pairs where one range fully contains the other
-    let ranges = read_input("input/day04.in");
-    let mut count = 0;
-    for (range1, range2) in ranges.iter() {
-        if (range1.start <= range2.start && range1.end >= range2.end)
-            || (range2.start <= range1.start && range2.end >= range1.end)
-        {
-            count += 1;
-        }
+// how many pairs there are of each relation, in the order of `Relation::ALL`
+pub fn histogram(input: &str) -> [(Relation, usize); 13] {
+    let mut counts = Relation::ALL.map(|relation| (relation, 0));
+    for (range1, range2) in parse_input(input) {
+        counts[range1.relation(&range2) as usize].1 += 1;
     }
-    println!("{}", count);
+    counts
 }
 
-pub fn part2() {
+pub fn part1(input: &str) -> String {
+    // count the number of pairs where one range fully contains the other
+    let count = histogram(input)
+        .iter()
+        .filter(|(relation, _)| relation.is_containment())
+        .map(|(_, count)| count)
+        .sum::<usize>();
+    count.to_string()
+}
+
+pub fn part2(input: &str) -> String {
     // count the number of pairs where the ranges overlap
-    let ranges = read_input("input/day04.in");
-    let mut count = 0;
-    for (range1, range2) in ranges.iter() {
-        if (range1.start <= range2.start && range1.end >= range2.start)
-            || (range2.start <= range1.start && range2.end >= range1.start)
-        {
-            count += 1;
+    let count = histogram(input)
+        .iter()
+        .filter(|(relation, _)| !relation.is_disjoint())
+        .map(|(_, count)| count)
+        .sum::<usize>();
+    count.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn relations() {
+        let range = |start, end| Range { start, end };
+        let max = u32::MAX;
+        let cases = [
+            // one pair for each relation
+            (range(1, 2), range(4, 5), Relation::Before),
+            (range(1, 2), range(3, 5), Relation::Meets),
+            (range(1, 3), range(3, 5), Relation::Overlaps),
+            (range(3, 4), range(3, 5), Relation::Starts),
+            (range(4, 4), range(3, 5), Relation::During),
+            (range(4, 5), range(3, 5), Relation::Finishes),
+            (range(3, 5), range(3, 5), Relation::Equals),
+            (range(3, 5), range(4, 5), Relation::FinishedBy),
+            (range(3, 5), range(4, 4), Relation::Contains),
+            (range(3, 5), range(3, 4), Relation::StartedBy),
+            (range(3, 5), range(1, 3), Relation::OverlappedBy),
+            (range(3, 5), range(1, 2), Relation::MetBy),
+            (range(4, 5), range(1, 2), Relation::After),
+            // single sections
+            (range(3, 3), range(3, 3), Relation::Equals),
+            (range(3, 3), range(4, 4), Relation::Meets),
+            (range(3, 3), range(3, 5), Relation::Starts),
+            (range(5, 5), range(3, 5), Relation::Finishes),
+            // the last section there is
+            (range(0, max), range(0, max), Relation::Equals),
+            (range(1, max), range(max, max), Relation::FinishedBy),
+            (range(0, max - 1), range(max, max), Relation::Meets),
+        ];
+        for (a, b, relation) in cases {
+            assert_eq!(a.relation(&b), relation, "{:?} {:?}", a, b);
+            assert_eq!(b.relation(&a), relation.inverse(), "{:?} {:?}", b, a);
         }
+        let covered: HashSet<Relation> = cases[..13].iter().map(|case| case.2).collect();
+        assert_eq!(covered.len(), 13);
+
+        assert_eq!("2-4".parse(), Ok(range(2, 4)));
+        assert!("4-2".parse::<Range>().is_err());
+        assert!("2".parse::<Range>().is_err());
+
+        let example = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+        let histogram = histogram(example);
+        assert_eq!(histogram[Relation::Contains as usize].1, 1);
+        assert_eq!(histogram[Relation::Meets as usize].1, 1);
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 6);
+        assert_eq!(part1(example), "2");
+        assert_eq!(part2(example), "4");
     }
-    println!("{}", count);
 }
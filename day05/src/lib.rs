@@ -1,5 +1,3 @@
-use std::fs;
-
 // each column is a stack
 //     [D]
 // [N] [C]
@@ -10,10 +8,9 @@ use std::fs;
 // move 3 from 1 to 3
 // move 2 from 2 to 1
 // move 1 from 1 to 2
-fn read_input(file: &str) -> (Vec<Vec<char>>, Vec<Instr>) {
+fn parse_input(input: &str) -> (Vec<Vec<char>>, Vec<Instr>) {
     // read each vertical line and add the numbers to a stack
 
-    let input = fs::read_to_string(file).unwrap();
     //split at double newline
     let mut parts = input.split("\n\n");
 
@@ -55,29 +52,23 @@ struct Instr {
     to: usize,
 }
 
-pub fn part1() {
-    let (mut stacks, instructions) = read_input("input/day05.in");
+// moves crates one at a time, like the CrateMover 9000
+fn move_one_at_a_time(stacks: &mut [Vec<char>], instructions: &[Instr]) {
     for instr in instructions {
+        // an empty stack has nothing to give, the move still counts down
+        // so it ends instead of waiting for crates that never come
         let mut amount = instr.amount;
         while amount > 0 {
             if let Some(c) = stacks[instr.from - 1].pop() {
                 stacks[instr.to - 1].push(c);
-                amount -= 1;
             }
+            amount -= 1;
         }
     }
-
-    //take the top char from each stack
-    let mut result = String::new();
-    for stack in stacks {
-        result.push(stack[stack.len() - 1]);
-    }
-    println!("Result: {}", result);
 }
 
-pub fn part2() {
-    // same as part1, but all chars move in the same order they were in
-    let (mut stacks, instructions) = read_input("input/day05.in");
+// moves all crates at once, keeping their order, like the CrateMover 9001
+fn move_all_at_once(stacks: &mut [Vec<char>], instructions: &[Instr]) {
     for instr in instructions {
         //take top amount of chars from from stack
         let mut chars: Vec<char> = Vec::new();
@@ -91,11 +82,45 @@ pub fn part2() {
             stacks[instr.to - 1].push(*c);
         }
     }
+}
+
+//take the top char from each stack, empty stacks are skipped
+fn top_crates(stacks: &[Vec<char>]) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
+
+pub fn part1(input: &str) -> String {
+    let (mut stacks, instructions) = parse_input(input);
+    move_one_at_a_time(&mut stacks, &instructions);
+    top_crates(&stacks)
+}
+
+pub fn part2(input: &str) -> String {
+    // same as part1, but all chars move in the same order they were in
+    let (mut stacks, instructions) = parse_input(input);
+    move_all_at_once(&mut stacks, &instructions);
+    top_crates(&stacks)
+}
 
-    //take the top char from each stack
-    let mut result = String::new();
-    for stack in stacks {
-        result.push(stack[stack.len() - 1]);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generators::day05::{input, oracle, Size};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn one_at_a_time_matches_oracle(input in input(Size::default())) {
+            let (mut stacks, instructions) = parse_input(&input);
+            move_one_at_a_time(&mut stacks, &instructions);
+            prop_assert_eq!(top_crates(&stacks), oracle(&input, false));
+        }
+
+        #[test]
+        fn all_at_once_matches_oracle(input in input(Size::default())) {
+            let (mut stacks, instructions) = parse_input(&input);
+            move_all_at_once(&mut stacks, &instructions);
+            prop_assert_eq!(top_crates(&stacks), oracle(&input, true));
+        }
     }
-    println!("Result: {}", result);
 }
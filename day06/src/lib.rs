@@ -1,8 +1,6 @@
 use std::collections::HashSet;
-use std::fs;
 
-fn read_input(file: &str) -> Vec<char> {
-    let input = fs::read_to_string(file).expect("Something went wrong reading the file");
+fn parse_input(input: &str) -> Vec<char> {
     input.chars().collect()
 }
 
@@ -16,12 +14,12 @@ pub fn find_first_marker(message: Vec<char>, window_size: usize) -> usize {
         + window_size
 }
 
-pub fn part1() {
-    let message = read_input("input/day06.in");
-    println!("{}", find_first_marker(message, 4));
+pub fn part1(input: &str) -> String {
+    let message = parse_input(input);
+    find_first_marker(message, 4).to_string()
 }
 
-pub fn part2() {
-    let message = read_input("input/day06.in");
-    println!("{}", find_first_marker(message, 14));
+pub fn part2(input: &str) -> String {
+    let message = parse_input(input);
+    find_first_marker(message, 14).to_string()
 }
@@ -62,9 +62,8 @@ impl Directory {
     }
 }
 
-fn read_input(file: &str) -> Rc<RefCell<Directory>> {
+fn parse_input(input: &str) -> Rc<RefCell<Directory>> {
     // commands start with $
-    let input = std::fs::read_to_string(file).unwrap();
     let mut lines = input.lines();
     let root = Rc::new(RefCell::new(Directory::new()));
     let mut current_directory = Rc::clone(&root);
@@ -78,7 +77,7 @@ fn read_input(file: &str) -> Rc<RefCell<Directory>> {
                         if let Some(parent) = &current_directory.borrow().parent {
                             Rc::clone(parent)
                         } else {
-                            println!("Cannot cd .. from root");
+                            // the root is its own parent
                             current_directory.clone()
                         }
                     }
@@ -106,45 +105,90 @@ fn read_input(file: &str) -> Rc<RefCell<Directory>> {
                     .files
                     .insert(name.to_string(), size);
             }
-            _ => {
-                println!("Invalid command");
-            }
+            // anything else isn't part of the terminal output, so it's skipped
+            _ => {}
         }
     }
     root
 }
 
+// adds an empty child directory, unless we have already seen one with that name
 fn insert_child(current_directory: Rc<RefCell<Directory>>, name: &str) {
-    let mut dir = Directory::new();
-    dir.parent = Some(Rc::clone(&current_directory));
-
+    let parent = Rc::clone(&current_directory);
     current_directory
         .borrow_mut()
         .children
-        .insert(name.to_string(), Rc::new(RefCell::new(dir)));
+        .entry(name.to_string())
+        .or_insert_with(|| {
+            let mut dir = Directory::new();
+            dir.parent = Some(parent);
+            Rc::new(RefCell::new(dir))
+        });
 }
 
-pub fn part1() {
-    let root = read_input("input/day07.in");
-
-    let sum: usize = root
-        .borrow_mut()
+// the puzzle asks for directories of at most 100000
+fn sum_of_small_directories(root: &Rc<RefCell<Directory>>) -> usize {
+    root.borrow_mut()
         .directories_size()
         .iter()
-        .filter(|x| **x < 100000)
-        .sum();
-
-    println!("Sum: {}", sum);
+        .filter(|x| **x <= 100000)
+        .sum()
 }
 
-pub fn part2() {
-    let total_disk_space = 70000000;
-    let space_needed = 30000000;
-
-    let root = read_input("input/day07.in");
+// the size of the smallest directory that frees up enough space when deleted,
+// one that frees exactly what's needed is enough
+fn smallest_directory_to_delete(
+    root: &Rc<RefCell<Directory>>,
+    total_disk_space: usize,
+    space_needed: usize,
+) -> usize {
     let used = root.borrow_mut().size();
-    let need_to_free = space_needed - (total_disk_space - used);
+    let need_to_free = space_needed.saturating_sub(total_disk_space.saturating_sub(used));
     let dirs = root.borrow_mut().directories_size();
-    let min = dirs.iter().filter(|x| **x > need_to_free).min().unwrap();
-    println!("Min: {}", min);
+    *dirs.iter().filter(|x| **x >= need_to_free).min().unwrap()
+}
+
+pub fn part1(input: &str) -> String {
+    let root = parse_input(input);
+    sum_of_small_directories(&root).to_string()
+}
+
+params::params! {
+    pub struct Params {
+        total_disk_space: usize = 70000000,
+        // free space the update needs
+        space_needed: usize = 30000000,
+    }
+}
+
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
+}
+
+pub fn part2_with(input: &str, params: &Params) -> String {
+    let root = parse_input(input);
+    smallest_directory_to_delete(&root, params.total_disk_space, params.space_needed).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generators::day07::{input, oracle_sizes, oracle_to_delete, Size};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn small_directories_match_oracle(input in input(Size::default())) {
+            let expected: usize = oracle_sizes(&input).iter().filter(|x| **x <= 100000).sum();
+            prop_assert_eq!(sum_of_small_directories(&parse_input(&input)), expected);
+        }
+
+        // files big enough that about 40% of the trees leave too little space for the update
+        #[test]
+        fn directory_to_delete_matches_oracle(input in input(Size { file_size: 5000000, ..Size::default() })) {
+            let expected = oracle_to_delete(&input);
+            prop_assume!(expected.is_some(), "the files don't fit on the disk");
+            prop_assert_eq!(part2(&input), expected.unwrap().to_string());
+        }
+    }
 }
@@ -166,8 +166,7 @@ enum Direction {
     Bottom,
 }
 
-fn read_input(file: &str) -> TreeGrid {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> TreeGrid {
     let grid: Vec<Vec<u32>> = input
         .lines()
         .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
@@ -175,14 +174,14 @@ fn read_input(file: &str) -> TreeGrid {
     TreeGrid::from_vector(grid)
 }
 
-pub fn part1() {
-    let grid = read_input("input/day08.in");
+pub fn part1(input: &str) -> String {
+    let grid = parse_input(input);
     let trees = grid.count_visible_trees();
-    println!("Part 1: {}", trees);
+    trees.to_string()
 }
 
-pub fn part2() {
-    let grid = read_input("input/day08.in");
+pub fn part2(input: &str) -> String {
+    let grid = parse_input(input);
     let score = grid.get_best_scenic_score();
-    println!("Part 2: {}", score);
+    score.to_string()
 }
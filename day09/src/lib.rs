@@ -24,8 +24,7 @@ struct Move {
 
 type Point = (i32, i32);
 
-fn read_input(file: &str) -> Vec<Move> {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> Vec<Move> {
     let changes = input
         .lines()
         .map(|line| {
@@ -80,12 +79,12 @@ fn solve<const NUM_KNOTS: usize>(moves: Vec<Move>) -> usize {
     tail_visited.len()
 }
 
-pub fn part1() {
-    let moves = read_input("input/day09.in");
-    println!("{}", solve::<2>(moves))
+pub fn part1(input: &str) -> String {
+    let moves = parse_input(input);
+    solve::<2>(moves).to_string()
 }
 
-pub fn part2() {
-    let moves = read_input("input/day09.in");
-    println!("{}", solve::<10>(moves))
+pub fn part2(input: &str) -> String {
+    let moves = parse_input(input);
+    solve::<10>(moves).to_string()
 }
@@ -1,3 +1,5 @@
+use trace::Tracer;
+
 enum Instruction {
     AddX(i32), // takes two cycles
     NoOp,      // takes one cycle
@@ -25,23 +27,24 @@ impl CRT {
         self.pixels[x as usize] = true;
     }
 
-    fn print(&self) {
+    fn render(&self) -> String {
+        let mut s = String::new();
         for y in 0..self.h {
             for x in 0..self.w {
                 let pixel = self.pixels[(y * self.w + x) as usize];
                 if pixel {
-                    print!("#");
+                    s.push('#');
                 } else {
-                    print!(".");
+                    s.push('.');
                 }
             }
-            println!();
+            s.push('\n');
         }
+        s
     }
 }
 
-fn read_input(file: &str) -> Vec<Instruction> {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> Vec<Instruction> {
     let instr = input
         .lines()
         .map(|line| {
@@ -57,8 +60,52 @@ fn read_input(file: &str) -> Vec<Instruction> {
     instr
 }
 
-pub fn part1() {
-    let instructions = read_input("input/day10.in");
+// runs the program and calls f with the cycle number and the value of X during that cycle
+// stops early if f returns false
+fn for_each_cycle(instructions: &[Instruction], mut f: impl FnMut(i32, i32) -> bool) {
+    let mut x = 1;
+    let mut c = 1;
+    for instr in instructions {
+        match instr {
+            Instruction::NoOp => {
+                if !f(c, x) {
+                    return;
+                }
+                c += 1;
+            }
+            Instruction::AddX(n) => {
+                for _ in 0..2 {
+                    if !f(c, x) {
+                        return;
+                    }
+                    c += 1;
+                }
+                x += n;
+            }
+        }
+    }
+}
+
+// draws the CRT once per cycle, keeping the row being drawn in view
+pub fn animate(input: &str, frame: &mut dyn FnMut(&str, usize) -> bool) {
+    let instructions = parse_input(input);
+    let mut crt = CRT::new(6, 40);
+    for_each_cycle(&instructions, |c, x| {
+        let pixel = (c - 1) % 40;
+        if pixel >= x - 1 && pixel <= x + 1 {
+            crt.set_pixel(c - 1);
+        }
+        frame(&crt.render(), ((c - 1) / 40) as usize)
+    });
+}
+
+pub fn part1(input: &str) -> String {
+    part1_traced(input, &mut Tracer::off())
+}
+
+// traces the register during every cycle and the signal strength at the cycles that count
+pub fn part1_traced(input: &str, tracer: &mut Tracer) -> String {
+    let instructions = parse_input(input);
     let mut instructions = instructions.iter().peekable();
     let mut current_instr: (Instruction, u32) = (Instruction::NoOp, 0);
     let mut x = 1;
@@ -77,10 +124,14 @@ pub fn part1() {
             }
         }
 
+        tracer.event("register", &[("cycle", c as i64), ("x", x as i64)]);
         match c {
             20 | 60 | 100 | 140 | 180 | 220 => {
                 signal_strength_sum += x * c;
-                println!("{}: {}", c, x * c);
+                tracer.event(
+                    "signal",
+                    &[("cycle", c as i64), ("strength", (x * c) as i64)],
+                );
             }
             _ => {}
         }
@@ -95,15 +146,18 @@ pub fn part1() {
             }
         }
 
-        println!("{}: {}", c, x);
-
         c += 1;
     }
-    println!("Part 1: {}", signal_strength_sum);
+    signal_strength_sum.to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    part2_traced(input, &mut Tracer::off())
 }
 
-pub fn part2() {
-    let instructions = read_input("input/day10.in");
+// traces the register during every cycle and every pixel that is lit
+pub fn part2_traced(input: &str, tracer: &mut Tracer) -> String {
+    let instructions = parse_input(input);
     let mut instructions = instructions.iter().peekable();
     let mut current_instr: (Instruction, u32) = (Instruction::NoOp, 0);
     let mut x = 1;
@@ -117,17 +171,17 @@ pub fn part2() {
                 }
                 Some(Instruction::AddX(n)) => {
                     current_instr = (Instruction::AddX(*n), 2);
-                    println!("Begin executing addx {} at cycle {}", n, c);
                 }
                 None => panic!("Ran out of instructions"),
             }
         }
 
         // draw pixel
+        tracer.event("register", &[("cycle", c as i64), ("x", x as i64)]);
         let pixel = (c - 1) % 40;
         if pixel >= x - 1 && pixel <= x + 1 {
             crt.set_pixel(c - 1);
-            println!("During cycle {}: CRT drawing at {}", c, c - 1);
+            tracer.mark("pixel", pixel as i64, ((c - 1) / 40) as i64, '#');
         }
 
         current_instr.1 -= 1;
@@ -136,12 +190,10 @@ pub fn part2() {
                 Instruction::NoOp => {}
                 Instruction::AddX(n) => {
                     x += n;
-                    println!("End of cycle {}: Register is now {}", c, x)
                 }
             }
         }
         c += 1;
-        println!();
     }
-    crt.print();
+    crt.render()
 }
@@ -26,12 +26,25 @@ impl FromStr for Operation {
 }
 
 impl Operation {
-    fn apply(&self, a: u64) -> u64 {
-        match self {
+    #[cfg(not(feature = "checked-arith"))]
+    fn apply(&self, a: u64) -> Result<u64, String> {
+        Ok(match self {
             Operation::Add(b) => a + *b as u64,
             Operation::Mult(b) => a * *b as u64,
             Operation::Square => a * a,
-        }
+        })
+    }
+
+    // worry levels grow fast, without the modulo in part 2 they'd wrap around
+    // in a release build and send items to the wrong monkey
+    #[cfg(feature = "checked-arith")]
+    fn apply(&self, a: u64) -> Result<u64, String> {
+        let (new, operation) = match self {
+            Operation::Add(b) => (a.checked_add(*b as u64), format!("{} + {}", a, b)),
+            Operation::Mult(b) => (a.checked_mul(*b as u64), format!("{} * {}", a, b)),
+            Operation::Square => (a.checked_mul(a), format!("{} * {}", a, a)),
+        };
+        new.ok_or_else(|| format!("day 11: worry level {} overflows u64", operation))
     }
 }
 
@@ -43,8 +56,7 @@ struct Monkey {
     throw_false: usize,
 }
 
-fn read_input(input: &str) -> Option<Vec<Monkey>> {
-    let input = std::fs::read_to_string(input).unwrap();
+fn parse_input(input: &str) -> Option<Vec<Monkey>> {
     let mut lines = input.lines().into_iter().peekable();
     let mut monkeys = Vec::new();
     while lines.peek().is_some() {
@@ -93,10 +105,26 @@ fn read_input(input: &str) -> Option<Vec<Monkey>> {
     Some(monkeys)
 }
 
-pub fn part1() {
-    let mut monkeys = read_input("input/day11.in").unwrap();
+params::params! {
+    pub struct Params {
+        rounds_part1: usize = 20,
+        rounds_part2: usize = 10000,
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    part1_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> String {
+    try_part1_with(input, params).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// like `part1_with`, but an overflow found by the checked-arith feature is an error
+pub fn try_part1_with(input: &str, params: &Params) -> Result<String, String> {
+    let mut monkeys = parse_input(input).unwrap();
     let mut monkey_count = vec![0; monkeys.len()];
-    for _ in 0..20 {
+    for _ in 0..params.rounds_part1 {
         for i in 0..monkeys.len() {
             let monkey = &mut monkeys[i];
             let items = monkey.items.drain(..).collect::<Vec<_>>();
@@ -109,7 +137,7 @@ pub fn part1() {
             } = *monkey;
             monkey_count[i] += items.len();
             for item in items {
-                let mut item = operation.apply(item);
+                let mut item = operation.apply(item)?;
                 item /= 3;
 
                 let other_monkey: usize = if item % divisible_test == 0 {
@@ -122,19 +150,27 @@ pub fn part1() {
         }
     }
     monkey_count.sort();
-    println!("{:?}", monkey_count);
     // multiply the last two
     let val = monkey_count.iter().rev().take(2).product::<usize>();
-    println!("{}", val);
+    Ok(val.to_string())
+}
+
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
 }
 
-pub fn part2() {
-    let mut monkeys = read_input("input/day11.in").unwrap();
+pub fn part2_with(input: &str, params: &Params) -> String {
+    try_part2_with(input, params).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// like `part2_with`, but an overflow found by the checked-arith feature is an error
+pub fn try_part2_with(input: &str, params: &Params) -> Result<String, String> {
+    let mut monkeys = parse_input(input).unwrap();
     let modulo_divisor = monkeys
         .iter()
         .fold(1, |acc, x| acc * x.divisible_test as u64);
     let mut monkey_count = vec![0; monkeys.len()];
-    for _ in 0..10000 {
+    for _ in 0..params.rounds_part2 {
         for i in 0..monkeys.len() {
             let monkey = &mut monkeys[i];
             let items = monkey.items.drain(..).collect::<Vec<_>>();
@@ -147,7 +183,7 @@ pub fn part2() {
             } = *monkey;
             monkey_count[i] += items.len();
             for item in items {
-                let mut item = operation.apply(item);
+                let mut item = operation.apply(item)?;
                 item = item % modulo_divisor;
 
                 let other_monkey: usize = if item % divisible_test == 0 {
@@ -160,8 +196,59 @@ pub fn part2() {
         }
     }
     monkey_count.sort();
-    println!("{:?}", monkey_count);
     // multiply the last two
     let val = monkey_count.iter().rev().take(2).product::<usize>();
-    println!("{}", val);
+    Ok(val.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE), "10605");
+        assert_eq!(part2(EXAMPLE), "2713310158");
+    }
+
+    // dividing by 3 doesn't keep the worry levels down for long
+    // once part 1 runs for as many rounds as part 2
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn overflow_is_an_error() {
+        let params = Params {
+            rounds_part1: 10000,
+            ..Params::default()
+        };
+        let error = try_part1_with(EXAMPLE, &params).unwrap_err();
+        assert!(error.starts_with("day 11: worry level"), "{}", error);
+    }
 }
@@ -1,8 +1,10 @@
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     str::FromStr,
 };
 
+use trace::Tracer;
+
 type Point = (usize, usize);
 
 #[derive(Debug)]
@@ -98,23 +100,90 @@ impl Grid {
         }
         None
     }
+
+    // like `bfs`, but remembers where each point was reached from
+    // so the path itself can be walked back from `to`
+    fn bfs_path(&self, from: Point, to: Point) -> Option<Vec<Point>> {
+        let mut queue = VecDeque::new();
+        let mut came_from = HashMap::new();
+        queue.push_back(from);
+        came_from.insert(from, from);
+        while let Some(point) = queue.pop_front() {
+            if point == to {
+                let mut path = vec![to];
+                while *path.last().unwrap() != from {
+                    path.push(came_from[path.last().unwrap()]);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for neighbour in self.get_possible_neighbours(point) {
+                came_from.entry(neighbour).or_insert_with(|| {
+                    queue.push_back(neighbour);
+                    point
+                });
+            }
+        }
+        None
+    }
+
+    // a single search starting from every point at once,
+    // so it finds the distance from whichever is closest
+    fn multi_source_bfs(&self, from: Vec<Point>, to: Point) -> Option<usize> {
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        for point in from {
+            if visited.insert(point) {
+                queue.push_back((point, 0));
+            }
+        }
+        while let Some((point, distance)) = queue.pop_front() {
+            if point == to {
+                return Some(distance);
+            }
+            for neighbour in self.get_possible_neighbours(point) {
+                if visited.insert(neighbour) {
+                    queue.push_back((neighbour, distance + 1));
+                }
+            }
+        }
+        None
+    }
 }
 
-fn read_input(file: &str) -> Grid {
-    std::fs::read_to_string(file).unwrap().parse().unwrap()
+fn parse_input(input: &str) -> Grid {
+    input.parse().unwrap()
 }
 
-pub fn part1() {
-    let grid = read_input("input/day12.in");
-    println!("{:?}", grid);
+pub fn part1(input: &str) -> String {
+    let grid = parse_input(input);
 
     let path_len = grid.bfs(grid.start, grid.end);
 
-    println!("Part 1: {}", path_len.unwrap_or(std::usize::MAX));
+    path_len.unwrap_or(std::usize::MAX).to_string()
+}
+
+// the height map as letters, then the shortest path walked from the start
+pub fn part1_traced(input: &str, tracer: &mut Tracer) -> String {
+    let grid = parse_input(input);
+    for (y, row) in grid.grid.iter().enumerate() {
+        for (x, height) in row.iter().enumerate() {
+            let glyph = char::from(b'a' + *height as u8);
+            tracer.mark("height", x as i64, y as i64, glyph);
+        }
+    }
+
+    let Some(path) = grid.bfs_path(grid.start, grid.end) else {
+        return usize::MAX.to_string();
+    };
+    for (x, y) in &path {
+        tracer.mark("step", *x as i64, *y as i64, '*');
+    }
+    (path.len() - 1).to_string()
 }
 
-pub fn part2() {
-    let grid = read_input("input/day12.in");
+pub fn part2(input: &str) -> String {
+    let grid = parse_input(input);
 
     //find shortest path from any point to end
     let mut paths = Vec::new();
@@ -127,5 +196,20 @@ pub fn part2() {
             paths.push(path_len);
         }
     }
-    println!("Part 2: {}", paths.iter().min().unwrap());
+    paths.iter().min().unwrap().to_string()
+}
+
+// same as part2, but with one search from all the lowest points instead of one per point
+pub fn part2_multi_source(input: &str) -> String {
+    let grid = parse_input(input);
+    let mut lowest = Vec::new();
+    for (y, row) in grid.grid.iter().enumerate() {
+        for (x, height) in row.iter().enumerate() {
+            if *height == 0 {
+                lowest.push((x, y));
+            }
+        }
+    }
+    let path_len = grid.multi_source_bfs(lowest, grid.end);
+    path_len.unwrap_or(usize::MAX).to_string()
 }
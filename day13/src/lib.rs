@@ -135,8 +135,7 @@ impl Ord for List {
     }
 }
 
-fn read_input(file: &str) -> Vec<List> {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> Vec<List> {
     let input = input.trim();
     let mut input = input.split("\n\n");
 
@@ -151,9 +150,9 @@ fn read_input(file: &str) -> Vec<List> {
     list
 }
 
-pub fn part1() {
+pub fn part1(input: &str) -> String {
     //for each pair, determine if they are in the right order
-    let list = read_input("input/day13.in");
+    let list = parse_input(input);
 
     let mut right_order = vec![];
     for (i, (list1, list2)) in list.iter().tuples().enumerate() {
@@ -161,11 +160,11 @@ pub fn part1() {
             right_order.push(i + 1);
         }
     }
-    println!("Part 1: {:?}", right_order.iter().sum::<usize>());
+    right_order.iter().sum::<usize>().to_string()
 }
 
-pub fn part2() {
-    let mut list = read_input("input/day13.in");
+pub fn part2(input: &str) -> String {
+    let mut list = parse_input(input);
     let divider1: List = "[[2]]]".parse().unwrap();
     let divider2: List = "[[6]]]".parse().unwrap();
     list.push(divider1.clone());
@@ -174,5 +173,5 @@ pub fn part2() {
     let index = list.iter().position(|x| x == &divider1).unwrap();
     let index2 = list.iter().position(|x| x == &divider2).unwrap();
 
-    println!("Part 2: {}", (index + 1) * (index2 + 1));
+    ((index + 1) * (index2 + 1)).to_string()
 }
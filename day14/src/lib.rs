@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use trace::Tracer;
+
 type Pos = (i32, i32);
 
 struct Cave {
@@ -10,6 +12,12 @@ struct Cave {
 
 impl std::fmt::Debug for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\n{}", self.render())
+    }
+}
+
+impl Cave {
+    fn render(&self) -> String {
         let mut s = String::new();
         let mut min_x = std::i32::MAX;
         let mut max_x = std::i32::MIN;
@@ -29,7 +37,6 @@ impl std::fmt::Debug for Cave {
                 max_y = *y;
             }
         }
-        s.push('\n');
         for y in min_y..=max_y {
             for x in min_x..=max_x {
                 if self.grid.contains(&(x, y)) {
@@ -40,11 +47,9 @@ impl std::fmt::Debug for Cave {
             }
             s.push('\n');
         }
-        write!(f, "{}", s)
+        s
     }
-}
 
-impl Cave {
     fn from_lines(lines: Vec<Line>) -> Cave {
         let mut cave = Cave {
             grid: HashSet::new(),
@@ -75,12 +80,12 @@ impl Cave {
 
     // sand drops down until it hits a something, then it goes down left, if it can't go down left, it goes down right
     // otherwise it stops
-    // returns true if comes to rest
-    // returns false if it falls indefinitely
-    fn drop_sand(&mut self, pos: Pos) -> bool {
+    // returns where the sand comes to rest
+    // returns None if it falls indefinitely
+    fn drop_sand(&mut self, pos: Pos) -> Option<Pos> {
         let mut current_pos = pos;
         if self.grid.contains(&current_pos) {
-            return false;
+            return None;
         }
         let mut lowest_point = self.grid.iter().map(|(_, y)| *y).max().unwrap();
 
@@ -90,7 +95,7 @@ impl Cave {
 
         loop {
             if current_pos.1 > lowest_point {
-                return false;
+                return None;
             }
 
             let is_below_ground = |pos: &Pos| -> bool {
@@ -129,7 +134,7 @@ impl Cave {
             self.grid.insert(current_pos);
             break;
         }
-        true
+        Some(current_pos)
     }
 }
 
@@ -139,8 +144,7 @@ struct Line {
     end: Pos,
 }
 
-fn read_input(file: &str) -> Vec<Line> {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> Vec<Line> {
     input
         .lines()
         .flat_map(|line| {
@@ -179,18 +183,57 @@ fn read_input(file: &str) -> Vec<Line> {
         .collect()
 }
 
-pub fn part1() {
-    let lines = read_input("input/day14.in");
+// draws the cave once per grain of sand that comes to rest,
+// keeping the row where it landed in view
+pub fn animate(input: &str, frame: &mut dyn FnMut(&str, usize) -> bool) {
+    let lines = parse_input(input);
+    let mut cave = Cave::from_lines(lines);
+    while let Some((_, y)) = cave.drop_sand((500, 0)) {
+        let min_y = cave.grid.iter().map(|(_, y)| *y).min().unwrap();
+        if !frame(&cave.render(), (y - min_y) as usize) {
+            return;
+        }
+    }
+}
+
+// the rock first, then every grain of sand where it comes to rest.
+// the rock is only collected and sorted when something is listening
+fn trace_sand(cave: &mut Cave, tracer: &mut Tracer) {
+    if tracer.is_on() {
+        let mut rocks: Vec<&Pos> = cave.grid.iter().collect();
+        rocks.sort_by_key(|(x, y)| (*y, *x));
+        for (x, y) in rocks {
+            tracer.mark("rock", *x as i64, *y as i64, '#');
+        }
+        if let Some(ground) = cave.ground {
+            tracer.event("floor", &[("y", ground as i64)]);
+        }
+    }
+    while let Some((x, y)) = cave.drop_sand((500, 0)) {
+        tracer.mark("sand_at_rest", x as i64, y as i64, 'o');
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    part1_traced(input, &mut Tracer::off())
+}
+
+pub fn part1_traced(input: &str, tracer: &mut Tracer) -> String {
+    let lines = parse_input(input);
     let mut cave = Cave::from_lines(lines);
-    while cave.drop_sand((500, 0)) {}
-    println!("Units of sand at rest: {}", cave.sand_at_rest);
+    trace_sand(&mut cave, tracer);
+    cave.sand_at_rest.to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    part2_traced(input, &mut Tracer::off())
 }
 
-pub fn part2() {
-    let lines = read_input("input/day14.in");
+pub fn part2_traced(input: &str, tracer: &mut Tracer) -> String {
+    let lines = parse_input(input);
     let mut cave = Cave::from_lines(lines);
     let lowest_point = cave.grid.iter().map(|(_, y)| y).max().unwrap();
     cave.ground = Some(*lowest_point + 2);
-    while cave.drop_sand((500, 0)) {}
-    println!("Units of sand at rest: {}", cave.sand_at_rest);
+    trace_sand(&mut cave, tracer);
+    cave.sand_at_rest.to_string()
 }
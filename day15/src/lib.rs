@@ -1,5 +1,7 @@
 use std::{collections::HashSet, ops::Range, str::FromStr};
 
+use progress::{Cancelled, Progress, Stopped};
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 struct Pos(i64, i64);
 
@@ -89,7 +91,7 @@ impl Sensor {
     }
 }
 
-fn read_input(input: &str) -> Vec<Sensor> {
+fn parse_input(input: &str) -> Vec<Sensor> {
     input
         .lines()
         .map(|l| l.parse::<Sensor>().unwrap())
@@ -114,10 +116,22 @@ fn merge_ranges(ranges: Vec<Range<i64>>) -> Vec<Range<i64>> {
     merged
 }
 
-pub fn part1() {
-    let input = std::fs::read_to_string("input/day15.in").unwrap();
-    let sensors = read_input(&input);
-    let line = 2000000;
+params::params! {
+    pub struct Params {
+        // the row to count in part 1, the example uses 10
+        row: i64 = 2000000,
+        // the largest x and y the distress beacon can be at, the example uses 20
+        max: i64 = 4000000,
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    part1_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> String {
+    let sensors = parse_input(input);
+    let line = params.row;
     let coverage_at_line = sensors
         .iter()
         .filter_map(|s| s.coverage_at_line(line))
@@ -133,36 +147,76 @@ pub fn part1() {
         .len() as i64;
 
     let count = merged.iter().fold(0, |a, r| a + r.end - r.start);
-    println!("Part 1: {}", count - num_beacons);
+    (count - num_beacons).to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
 }
 
-pub fn part2() {
-    let input = std::fs::read_to_string("input/day15.in").unwrap();
-    let bounds = 0..4000000 + 1;
-    let sensors = read_input(&input);
+pub fn part2_with(input: &str, params: &Params) -> String {
+    try_part2_with(input, params).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// like `part2_with`, but an overflow found by the checked-arith feature is an error
+pub fn try_part2_with(input: &str, params: &Params) -> Result<String, String> {
+    let beacon = find_beacon(input, params, &Progress::new()).map_err(|e| e.to_string())?;
+    Ok(tuning_frequency(beacon)?.to_string())
+}
+
+// part 2 goes through millions of rows, so it reports how far it got
+// and stops when asked to, or with the overflow checked-arith found
+pub fn part2_progress(
+    input: &str,
+    params: &Params,
+    progress: &Progress,
+) -> Result<String, Stopped> {
+    let beacon = find_beacon(input, params, progress)?;
+    let frequency = tuning_frequency(beacon).map_err(Stopped::Failed)?;
+    Ok(frequency.to_string())
+}
+
+// the only position in the search area that no sensor covers
+fn find_beacon(input: &str, params: &Params, progress: &Progress) -> Result<Pos, Cancelled> {
+    let sensors = parse_input(input);
+    progress.start("rows", params.max as u64 + 1);
 
     // for each y, get a vector of ranges that are covered by sensors
-    let beacon = bounds
-        .clone()
-        .find_map(|y| {
-            // get all ranges that are covered by sensors at y
-            // and merge them
-            let ranges = sensors
-                .iter()
-                .filter_map(|s| s.coverage_at_line(y))
-                .collect::<Vec<_>>();
-            let merged_ranges = merge_ranges(ranges);
-            // println!("y: {}, merged: {:?}", y, merged_ranges);
-
-            if merged_ranges.len() == 2 {
-                return Some(Pos(merged_ranges[0].end, y));
-            }
-            None
-        })
-        .unwrap();
-    println!("Pos: {:?}", beacon);
-    let tuning_frequency = beacon.0 * 400000 + beacon.1;
-    println!("Part 2: {}", tuning_frequency);
+    let mut beacon = None;
+    for y in 0..params.max + 1 {
+        if y % 10000 == 0 {
+            progress.set(y as u64);
+            progress.check()?;
+        }
+
+        // get all ranges that are covered by sensors at y
+        // and merge them
+        let ranges = sensors
+            .iter()
+            .filter_map(|s| s.coverage_at_line(y))
+            .collect::<Vec<_>>();
+        let merged_ranges = merge_ranges(ranges);
+
+        if merged_ranges.len() == 2 {
+            beacon = Some(Pos(merged_ranges[0].end, y));
+            break;
+        }
+    }
+    Ok(beacon.unwrap())
+}
+
+#[cfg(not(feature = "checked-arith"))]
+fn tuning_frequency(beacon: Pos) -> Result<i64, String> {
+    Ok(beacon.0 * 4_000_000 + beacon.1)
+}
+
+#[cfg(feature = "checked-arith")]
+fn tuning_frequency(beacon: Pos) -> Result<i64, String> {
+    beacon
+        .0
+        .checked_mul(4_000_000)
+        .and_then(|x| x.checked_add(beacon.1))
+        .ok_or_else(|| format!("day 15: tuning frequency of {:?} overflows i64", beacon))
 }
 
 #[cfg(test)]
@@ -175,4 +229,42 @@ mod tests {
         let merged = merge_ranges(ranges);
         assert_eq!(merged, vec![0..7, 8..10, 11..13, 14..15]);
     }
+
+    #[test]
+    fn example() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+        let params = Params { row: 10, max: 20 };
+        assert_eq!(part1_with(input, &params), "26");
+        assert_eq!(part2_with(input, &params), "56000011");
+    }
+
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn overflow_is_an_error() {
+        assert_eq!(tuning_frequency(Pos(14, 11)), Ok(56000011));
+        let error = tuning_frequency(Pos(i64::MAX / 1000, 0)).unwrap_err();
+        assert!(error.starts_with("day 15:"), "{}", error);
+
+        // the only gap in row 0 is at x=3000000000002
+        let input = "Sensor at x=3000000000000, y=0: closest beacon is at x=3000000000001, y=0
+Sensor at x=3000000000004, y=0: closest beacon is at x=3000000000005, y=0";
+        let params = Params { row: 0, max: 0 };
+        match part2_progress(input, &params, &Progress::new()) {
+            Err(Stopped::Failed(error)) => assert!(error.starts_with("day 15:"), "{}", error),
+            other => panic!("Expected an overflow, got {:?}", other),
+        }
+    }
 }
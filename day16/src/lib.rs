@@ -11,6 +11,7 @@ use nom::{
 };
 
 use itertools::Itertools;
+use progress::{Cancelled, Progress};
 const NUM_VALVES: usize = 55;
 
 struct NamedValve<'a> {
@@ -66,9 +67,8 @@ impl Cave {
     }
 }
 
-fn read_input(file: &str) -> Cave {
-    let input = std::fs::read_to_string(file).unwrap();
-    let (_, valves) = all_consuming(separated_list1(line_ending, parse_valve))(&input).unwrap();
+fn parse_input(input: &str) -> Cave {
+    let (_, valves) = all_consuming(separated_list1(line_ending, parse_valve))(input).unwrap();
     let mut name_to_id = HashMap::new();
     valves.iter().for_each(|valve| {
         let id = name_to_id.len();
@@ -153,14 +153,26 @@ fn find_max_release(
     (max, path)
 }
 
+params::params! {
+    pub struct Params {
+        minutes_part1: usize = 30,
+        // part 2 loses some minutes to teaching the elephant
+        minutes_part2: usize = 26,
+    }
+}
+
 // After hours of trying to get it to work,
 // I gave up and found someone who made a similar idea
 // and changed mine to match theirs.
 // https://github.com/synapticarbors/advent_of_code_2022/blob/main/rust/aoc16/src/main.rs#L61
 // It was surprisingly similar to my original idea,
 // but it worked..
-pub fn part1() {
-    let cave = read_input("input/day16.in");
+pub fn part1(input: &str) -> String {
+    part1_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> String {
+    let cave = parse_input(input);
     let distances = cave.calc_distances();
 
     // println!("{:#?}", cave);
@@ -173,15 +185,35 @@ pub fn part1() {
     );
     // println!("{:#?}", valves_to_release);
 
-    let (release, _) = find_max_release(&distances, &cave, &mut valves_to_release, 30, cave.aa_id);
+    let (release, _) = find_max_release(
+        &distances,
+        &cave,
+        &mut valves_to_release,
+        params.minutes_part1,
+        cave.aa_id,
+    );
 
     // println!("{:#?}", path);
-    println!("Part 1: {}", release);
+    release.to_string()
 }
 
 // this part is completely my own idea
-pub fn part2() {
-    let cave = read_input("input/day16.in");
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
+}
+
+pub fn part2_with(input: &str, params: &Params) -> String {
+    part2_progress(input, params, &Progress::new()).unwrap()
+}
+
+// trying every split takes a while, so this reports how many splits are done
+// and stops between two splits when asked to
+pub fn part2_progress(
+    input: &str,
+    params: &Params,
+    progress: &Progress,
+) -> Result<String, Cancelled> {
+    let cave = parse_input(input);
     let distances = cave.calc_distances();
 
     let valves_to_release = cave
@@ -212,16 +244,31 @@ pub fn part2() {
     // for every pair of groups, find the max release
     // if the release is greater than the current max, update the max
     let mut max = 0;
+    progress.start("splits", all_groups.len() as u64);
     for (group1, group2) in all_groups {
+        progress.check()?;
         let mut group1 = HashSet::from_iter(group1);
         let mut group2 = HashSet::from_iter(group2);
-        let (release1, _) = find_max_release(&distances, &cave, &mut group1, 26, cave.aa_id);
-        let (release2, _) = find_max_release(&distances, &cave, &mut group2, 26, cave.aa_id);
+        let (release1, _) = find_max_release(
+            &distances,
+            &cave,
+            &mut group1,
+            params.minutes_part2,
+            cave.aa_id,
+        );
+        let (release2, _) = find_max_release(
+            &distances,
+            &cave,
+            &mut group2,
+            params.minutes_part2,
+            cave.aa_id,
+        );
         let release = release1 + release2;
         if release > max {
             max = release;
         }
+        progress.advance(1);
     }
 
-    println!("Part 2: {}", max);
+    Ok(max.to_string())
 }
@@ -2,6 +2,9 @@ use std::{
     collections::{HashMap, HashSet},
     ops::Range,
 };
+
+use trace::Tracer;
+
 const CHAMBER_WIDTH: usize = 7;
 const DEFAULT_ROW: [bool; CHAMBER_WIDTH] = [false; CHAMBER_WIDTH];
 const MAX_ROCKS_PART1: usize = 2022;
@@ -82,7 +85,8 @@ where
         }
     }
 
-    fn spawn_rock(&mut self, rock: RockType) {
+    // drops the rock until it comes to rest and returns where that is
+    fn spawn_rock(&mut self, rock: RockType) -> Pos {
         let mut pos: Pos = (2, self.highest_rock + 4);
 
         let mut down = false;
@@ -129,6 +133,7 @@ where
             row[x] = true;
         }
         self.highest_rock = self.highest_rock.max(*max_y);
+        pos
     }
 
     fn can_spawn(&self, spaces: &[Pos]) -> bool {
@@ -164,24 +169,30 @@ where
 
     #[allow(dead_code)]
     fn print(&self, pos: Pos, rock: RockType) {
+        println!("{}", self.render(pos, rock));
+    }
+
+    // draws the chamber from the top of the falling rock down to the floor
+    fn render(&self, pos: Pos, rock: RockType) -> String {
         let spaces = rock.get_spaces(pos);
         let spaces: HashSet<(usize, usize)> = spaces.iter().copied().collect();
-        for y in (1..=self.highest_rock + 4).rev() {
+        let mut s = String::new();
+        for y in (1..=self.highest_rock + 7).rev() {
             let row = self.grid.get(&y).unwrap_or(&DEFAULT_ROW);
-            print!("|");
+            s.push('|');
             for x in 0..CHAMBER_WIDTH {
                 if spaces.contains(&(x, y)) {
-                    print!("@");
+                    s.push('@');
                 } else if row[x] {
-                    print!("#");
+                    s.push('#');
                 } else {
-                    print!(".");
+                    s.push('.');
                 }
             }
-            println!("|");
+            s.push_str("|\n");
         }
-        println!("+-------+");
-        println!();
+        s.push_str("+-------+\n");
+        s
     }
 
     fn copy_section(&mut self, copy_range: Range<usize>, to_y: usize) {
@@ -203,8 +214,7 @@ struct State {
     rock_type: RockType,
 }
 
-fn read_input(file: &str) -> Vec<Direction> {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> Vec<Direction> {
     let mut winds = Vec::new();
     for char in input.trim().chars() {
         let wind = match char {
@@ -214,28 +224,74 @@ fn read_input(file: &str) -> Vec<Direction> {
         };
         winds.push(wind);
     }
-    // println!("winds: {:?}", winds);
     winds
 }
 
-pub fn part1() {
-    let winds = read_input("input/day17.example");
+// draws the chamber once per rock, with the next rock shown where it spawns
+// the top of the tower is always the first line of the frame
+pub fn animate(input: &str, frame: &mut dyn FnMut(&str, usize) -> bool) {
+    let winds = parse_input(input);
     let winds_cycle = winds.iter().cycle();
     let mut cave = Cave::new(winds_cycle);
-    for (i, rock_type) in ROCK_ORDER.iter().cycle().enumerate() {
-        println!("rock {}: {:?}", i, rock_type);
-        if i >= MAX_ROCKS_PART1 {
-            break;
+    for rock_type in ROCK_ORDER.iter().cycle().take(MAX_ROCKS_PART1) {
+        let spawn = (2, cave.highest_rock + 4);
+        if !frame(&cave.render(spawn, *rock_type), 0) {
+            return;
         }
         cave.spawn_rock(*rock_type);
     }
+}
+
+params::params! {
+    pub struct Params {
+        rocks_part1: usize = MAX_ROCKS_PART1,
+        rocks_part2: usize = MAX_ROCKS_PART2,
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    part1_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> String {
+    tower_height(input, params.rocks_part1, &mut Tracer::off())
+}
+
+// traces where each rock spawns and every space it fills once it comes to rest
+pub fn part1_traced(input: &str, tracer: &mut Tracer) -> String {
+    tower_height(input, MAX_ROCKS_PART1, tracer)
+}
+
+fn tower_height(input: &str, rocks: usize, tracer: &mut Tracer) -> String {
+    let winds = parse_input(input);
+    let winds_cycle = winds.iter().cycle();
+    let mut cave = Cave::new(winds_cycle);
+    tracer.y_up();
+    for (i, rock_type) in ROCK_ORDER.iter().cycle().take(rocks).enumerate() {
+        let spawn = (2, cave.highest_rock + 4);
+        tracer.event(
+            "rock_spawned",
+            &[
+                ("rock", i as i64),
+                ("x", spawn.0 as i64),
+                ("y", spawn.1 as i64),
+            ],
+        );
+        let rest = cave.spawn_rock(*rock_type);
+        for (x, y) in rock_type.get_spaces(rest) {
+            tracer.mark("rock_at_rest", x as i64, y as i64, '#');
+        }
+    }
+
+    cave.highest_rock.to_string()
+}
 
-    println!("Tallest rock: {}", cave.highest_rock);
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
 }
 
-pub fn part2() {
-    let time = std::time::Instant::now();
-    let winds = read_input("input/day17.in");
+pub fn part2_with(input: &str, params: &Params) -> String {
+    let winds = parse_input(input);
     let winds_cycle = winds.iter().cycle();
     let mut cave = Cave::new(winds_cycle);
 
@@ -244,8 +300,7 @@ pub fn part2() {
 
     let mut drops = 0;
     while let Some(rock_type) = rock_order.next() {
-        // println!("rock {}: {:?}", i, rock_type);
-        if drops >= MAX_ROCKS_PART2 {
+        if drops >= params.rocks_part2 {
             break;
         }
 
@@ -262,23 +317,14 @@ pub fn part2() {
 
         if let Some((prev_drops, prev_y)) = seen_states.insert(state, (drops, cave.highest_rock)) {
             let cycle_len = drops - prev_drops;
-            println!(
-                "Found cycle of length {} at {} drops, previous state at {} drops",
-                cycle_len, drops, prev_drops
-            );
-            let cycles = (MAX_ROCKS_PART2 - drops) / cycle_len;
-            println!("{} cycles", cycles);
+            let cycles = (params.rocks_part2 - drops) / cycle_len;
             drops += cycles * cycle_len;
             let copy_range = prev_y + 1..cave.highest_rock + 1;
-            println!("Copying range {:?}", copy_range);
             let to_y = cave.highest_rock + (cave.highest_rock - prev_y) * cycles;
-            println!("Copying to {}", to_y);
             cave.copy_section(copy_range, to_y);
             seen_states.clear();
         }
     }
 
-    let elapsed = time.elapsed();
-    println!("Tallest rock: {}", cave.highest_rock);
-    println!("Time: {}ms", elapsed.as_millis());
+    cave.highest_rock.to_string()
 }
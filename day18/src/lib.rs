@@ -1,5 +1,7 @@
 use std::{collections::HashSet, str::FromStr};
 
+use trace::Tracer;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Pos {
     x: i32,
@@ -163,24 +165,53 @@ impl Volume {
 }
 
 // each line is one point in the grid, with 3 values, x, y, z
-fn read_input(file: &str) -> Volume {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> Volume {
     input.parse().unwrap()
 }
 
-pub fn part1() {
-    let volume = read_input("input/day18.in");
+pub fn part1(input: &str) -> String {
+    let volume = parse_input(input);
     let surface_area = volume.get_lava_surface();
-    println!("Part 1: {}", surface_area);
+    surface_area.to_string()
 }
 
-pub fn part2() {
-    let mut volume = read_input("input/day18.in");
+pub fn part2(input: &str) -> String {
+    part2_traced(input, &mut Tracer::off())
+}
+
+// one slice per z next to each other, with the lava as '#'
+// and the air the steam couldn't reach as 'o'
+fn trace_slices(volume: &Volume, (lower, upper): (Pos, Pos), tracer: &mut Tracer) {
+    let width = (upper.x - lower.x + 1) as i64;
+    for z in lower.z..=upper.z {
+        tracer.event("slice", &[("z", z as i64)]);
+        let offset = (z - lower.z) as i64 * width;
+        for y in lower.y..=upper.y {
+            for x in lower.x..=upper.x {
+                let pos = Pos { x, y, z };
+                let glyph = if volume.lava.contains(&pos) {
+                    '#'
+                } else if volume.steam.contains(&pos) {
+                    continue;
+                } else {
+                    'o'
+                };
+                tracer.mark("voxel", offset + x as i64, y as i64, glyph);
+            }
+        }
+    }
+}
+
+pub fn part2_traced(input: &str, tracer: &mut Tracer) -> String {
+    let mut volume = parse_input(input);
 
     let (lower_bounds, upper_bounds) = volume.get_lava_bounds();
 
     let mut bounds = (lower_bounds, upper_bounds);
     volume.expand_steam_from(lower_bounds, &mut bounds);
+    if tracer.is_on() {
+        trace_slices(&volume, bounds, tracer);
+    }
     let exterior_surface = volume.get_lava_exterior_surface();
-    println!("Part 2: {}", exterior_surface);
+    exterior_surface.to_string()
 }
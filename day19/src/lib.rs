@@ -1,4 +1,5 @@
 use hashbrown::{HashMap, HashSet};
+use progress::{Cancelled, Progress};
 use rayon::prelude::*;
 use std::{collections::VecDeque, str::FromStr};
 
@@ -139,7 +140,13 @@ impl Factory {
     }
 }
 
-fn find_maximum_geodes(blueprint: &Blueprint, time: u32) -> u32 {
+// the search can take a while for a single blueprint,
+// so it checks every few thousand states whether it should stop
+fn find_maximum_geodes(
+    blueprint: &Blueprint,
+    time: u32,
+    progress: &Progress,
+) -> Result<u32, Cancelled> {
     let mut queue: VecDeque<(u32, Factory)> = VecDeque::new();
     let mut visited: HashSet<Factory> = HashSet::new();
     let mut max_at_t: HashMap<u32, u32> = HashMap::new();
@@ -157,7 +164,12 @@ fn find_maximum_geodes(blueprint: &Blueprint, time: u32) -> u32 {
     };
 
     queue.push_back((time_remaining, factory));
+    let mut states = 0u64;
     while let Some((time_remaining, factory)) = queue.pop_front() {
+        states += 1;
+        if states.is_multiple_of(4096) {
+            progress.check()?;
+        }
         // println!("{} {:?}", time_remaining, factory);
 
         let potential_geodes = factory.geodes       // the geodes we already have
@@ -230,42 +242,76 @@ fn find_maximum_geodes(blueprint: &Blueprint, time: u32) -> u32 {
         queue.push_back((time_remaining - 1, new_factory));
     }
 
-    *max_at_t.entry(0).or_insert(0)
+    Ok(*max_at_t.entry(0).or_insert(0))
 }
 
-fn read_input(file: &str) -> Vec<Blueprint> {
-    let input = std::fs::read_to_string(file).unwrap();
+fn parse_input(input: &str) -> Vec<Blueprint> {
     input.lines().map(|line| line.parse().unwrap()).collect()
 }
 
-pub fn part1() {
-    let blueprints = read_input("input/day19.in");
+params::params! {
+    pub struct Params {
+        minutes_part1: u32 = 24,
+        minutes_part2: u32 = 32,
+        // part 2 only uses the first few blueprints
+        blueprints_part2: usize = 3,
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    part1_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> String {
+    part1_progress(input, params, &Progress::new()).unwrap()
+}
+
+pub fn part1_progress(
+    input: &str,
+    params: &Params,
+    progress: &Progress,
+) -> Result<String, Cancelled> {
+    let blueprints = parse_input(input);
+    progress.start("blueprints", blueprints.len() as u64);
 
     let quality_total = blueprints
         .par_iter()
         .enumerate()
         .map(|(i, blueprint)| {
-            let current_max = find_maximum_geodes(blueprint, 24);
-            println!("Max geodes with blueprint {}: {}", i + 1, current_max);
-            current_max * (i as u32 + 1)
+            let current_max = find_maximum_geodes(blueprint, params.minutes_part1, progress)?;
+            progress.advance(1);
+            Ok(current_max * (i as u32 + 1))
         })
-        .sum::<u32>();
+        .sum::<Result<u32, Cancelled>>()?;
+
+    Ok(quality_total.to_string())
+}
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
+}
 
-    println!("Quality: {}", quality_total);
+pub fn part2_with(input: &str, params: &Params) -> String {
+    part2_progress(input, params, &Progress::new()).unwrap()
 }
-pub fn part2() {
-    let blueprints = read_input("input/day19.in");
+
+pub fn part2_progress(
+    input: &str,
+    params: &Params,
+    progress: &Progress,
+) -> Result<String, Cancelled> {
+    let blueprints = parse_input(input);
+    let count = params.blueprints_part2.min(blueprints.len());
+    progress.start("blueprints", count as u64);
 
     let quality_total = blueprints
         .par_iter()
-        .take(3)
-        .enumerate()
-        .map(|(i, blueprint)| {
-            let current_max = find_maximum_geodes(blueprint, 32);
-            println!("Max geodes with blueprint {}: {}", i + 1, current_max);
-            current_max
+        .take(params.blueprints_part2)
+        .map(|blueprint| {
+            let current_max = find_maximum_geodes(blueprint, params.minutes_part2, progress)?;
+            progress.advance(1);
+            Ok(current_max)
         })
-        .product::<u32>();
+        .product::<Result<u32, Cancelled>>()?;
 
-    println!("Quality: {}", quality_total);
+    Ok(quality_total.to_string())
 }
@@ -24,56 +24,290 @@ impl EncryptedFile {
     }
 }
 
-fn read_input(file: &str, decryption_key: Option<i64>) -> EncryptedFile {
-    let input = std::fs::read_to_string(file).expect("Error reading input file!");
+#[derive(Clone, Copy)]
+struct Node {
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+    size: usize,
+    priority: u64,
+}
+
+// an implicit treap holding the order of the numbers
+// node i is the number at index i in the original list, and its position in the
+// mixed list is the number of nodes before it in order, so moving a number is
+// O(log n) instead of the O(n) `position`, `remove` and `insert` on a Vec
+struct TreapList {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl TreapList {
+    fn new(len: usize) -> Self {
+        // xorshift, the priorities only need to look random
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let nodes = (0..len)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                Node {
+                    left: None,
+                    right: None,
+                    parent: None,
+                    size: 1,
+                    priority: seed,
+                }
+            })
+            .collect();
+        let mut list = TreapList { nodes, root: None };
+        for i in 0..len {
+            list.root = list.merge(list.root, Some(i));
+        }
+        list
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |n| self.nodes[n].size)
+    }
+
+    fn update(&mut self, n: usize) {
+        let Node { left, right, .. } = self.nodes[n];
+        self.nodes[n].size = 1 + self.size(left) + self.size(right);
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(n);
+        }
+    }
+
+    // splits into the first k nodes and the rest
+    fn split(&mut self, node: Option<usize>, k: usize) -> (Option<usize>, Option<usize>) {
+        let Some(n) = node else {
+            return (None, None);
+        };
+        self.nodes[n].parent = None;
+        let left_size = self.size(self.nodes[n].left);
+        if k <= left_size {
+            let (l, r) = self.split(self.nodes[n].left, k);
+            self.nodes[n].left = r;
+            self.update(n);
+            (l, Some(n))
+        } else {
+            let (l, r) = self.split(self.nodes[n].right, k - left_size - 1);
+            self.nodes[n].right = l;
+            self.update(n);
+            (Some(n), r)
+        }
+    }
+
+    fn merge(&mut self, a: Option<usize>, b: Option<usize>) -> Option<usize> {
+        match (a, b) {
+            (None, b) => b,
+            (a, None) => a,
+            (Some(a), Some(b)) => {
+                if self.nodes[a].priority > self.nodes[b].priority {
+                    let right = self.merge(self.nodes[a].right, Some(b));
+                    self.nodes[a].right = right;
+                    self.update(a);
+                    Some(a)
+                } else {
+                    let left = self.merge(Some(a), self.nodes[b].left);
+                    self.nodes[b].left = left;
+                    self.update(b);
+                    Some(b)
+                }
+            }
+        }
+    }
+
+    // walks up to the root, counting everything that comes before the node
+    fn position(&self, n: usize) -> usize {
+        let mut position = self.size(self.nodes[n].left);
+        let mut current = n;
+        while let Some(parent) = self.nodes[current].parent {
+            if self.nodes[parent].right == Some(current) {
+                position += self.size(self.nodes[parent].left) + 1;
+            }
+            current = parent;
+        }
+        position
+    }
+
+    fn remove(&mut self, position: usize) {
+        let (before, rest) = self.split(self.root, position);
+        let (_, after) = self.split(rest, 1);
+        self.root = self.merge(before, after);
+    }
+
+    fn insert(&mut self, position: usize, n: usize) {
+        let (before, after) = self.split(self.root, position);
+        let before = self.merge(before, Some(n));
+        self.root = self.merge(before, after);
+    }
+
+    fn in_order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack = Vec::new();
+        let mut current = self.root;
+        while current.is_some() || !stack.is_empty() {
+            while let Some(n) = current {
+                stack.push(n);
+                current = self.nodes[n].left;
+            }
+            let n = stack.pop().unwrap();
+            order.push(n);
+            current = self.nodes[n].right;
+        }
+        order
+    }
+}
+
+impl EncryptedFile {
+    // same as mix, but keeps the order in a treap
+    fn mix_with(&mut self, list: &mut TreapList) {
+        for i in 0..self.original_list.len() {
+            let idx = list.position(i);
+            list.remove(idx);
+            let num = self.original_list[i];
+            let new_idx = (idx as i64 + num).rem_euclid(self.original_list.len() as i64 - 1);
+            list.insert(new_idx as usize, i);
+        }
+        self.indices = list.in_order();
+    }
+}
+
+// only a decryption key can make this fail, by overflowing with the checked-arith feature
+fn parse_input(input: &str, decryption_key: Option<i64>) -> Result<EncryptedFile, String> {
     let decryption_key = decryption_key.unwrap_or(1);
     let list: Vec<i64> = input
         .lines()
-        .map(|line| line.parse::<i64>().unwrap() * decryption_key)
-        .collect();
+        .map(|line| apply_key(line.parse::<i64>().unwrap(), decryption_key))
+        .collect::<Result<_, _>>()?;
 
-    EncryptedFile {
+    Ok(EncryptedFile {
         original_list: list.clone(),
         indices: (0..list.len()).collect(),
-    }
+    })
 }
 
-pub fn part1() {
-    let mut file = read_input("input/day20.in", None);
-    file.mix();
+#[cfg(not(feature = "checked-arith"))]
+fn apply_key(number: i64, decryption_key: i64) -> Result<i64, String> {
+    Ok(number * decryption_key)
+}
+
+// a larger key from the parameters can push the numbers past i64
+#[cfg(feature = "checked-arith")]
+fn apply_key(number: i64, decryption_key: i64) -> Result<i64, String> {
+    number.checked_mul(decryption_key).ok_or_else(|| {
+        format!(
+            "day 20: {} * decryption key {} overflows i64",
+            number, decryption_key
+        )
+    })
+}
+
+// sum of the values 1000, 2000 and 3000 places after the zero
+fn grove_coordinates(file: &EncryptedFile) -> i64 {
     let mixed_list = file.get_mixed_list();
     let zero_idx = mixed_list.iter().position(|&x| x == 0).unwrap();
-    // get value at 1000, 2000, 3000 after zero_idx
-    let sum_grove_coordinates = [1000, 2000, 3000]
+    [1000, 2000, 3000]
         .iter()
-        .map(|i| {
-            let idx = (zero_idx + i).rem_euclid(mixed_list.len());
-            println!("{}: {}", i, mixed_list[idx]);
-            mixed_list[idx]
-        })
-        .sum::<i64>();
-    println!("Sum of grove coordinates: {}", sum_grove_coordinates);
+        .map(|i| mixed_list[(zero_idx + i) % mixed_list.len()])
+        .sum()
 }
 
-pub fn part2() {
-    let mut file = read_input("input/day20.in", Some(DECRYPTION_KEY));
-    for _ in 1..=10 {
+params::params! {
+    pub struct Params {
+        decryption_key: i64 = DECRYPTION_KEY,
+        // how many times part 2 mixes the file
+        rounds: usize = 10,
+    }
+}
+
+pub fn part1(input: &str) -> String {
+    let mut file = parse_input(input, None).unwrap();
+    file.mix();
+    grove_coordinates(&file).to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    part2_with(input, &Params::default())
+}
+
+pub fn part2_with(input: &str, params: &Params) -> String {
+    try_part2_with(input, params).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// like `part2_with`, but an overflow found by the checked-arith feature is an error
+pub fn try_part2_with(input: &str, params: &Params) -> Result<String, String> {
+    let mut file = parse_input(input, Some(params.decryption_key))?;
+    for _ in 0..params.rounds {
         file.mix();
     }
+    Ok(grove_coordinates(&file).to_string())
+}
 
-    let mixed_list = file.get_mixed_list();
+pub fn part1_treap(input: &str) -> String {
+    let mut file = parse_input(input, None).unwrap();
+    let mut list = TreapList::new(file.original_list.len());
+    file.mix_with(&mut list);
+    grove_coordinates(&file).to_string()
+}
 
-    let zero_idx = mixed_list.iter().position(|&x| x == 0).unwrap();
-    println!("Zero index: {}", zero_idx);
-    // get value at 1000, 2000, 3000 after zero_idx
-    let sum_grove_coordinates = [1000, 2000, 3000]
-        .iter()
-        .map(|i| {
-            let idx = (zero_idx + i).rem_euclid(mixed_list.len());
-            println!("{}: {}", i, mixed_list[idx]);
-            mixed_list[idx] as i128
-        })
-        .sum::<i128>();
-
-    println!("Sum of grove coordinates: {}", sum_grove_coordinates);
+pub fn part2_treap(input: &str) -> String {
+    let mut file = parse_input(input, Some(DECRYPTION_KEY)).unwrap();
+    let mut list = TreapList::new(file.original_list.len());
+    for _ in 1..=10 {
+        file.mix_with(&mut list);
+    }
+    grove_coordinates(&file).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generators::day20::{input, oracle, Size};
+    use proptest::prelude::*;
+
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn key_overflow() {
+        let params = Params {
+            decryption_key: i64::MAX,
+            ..Params::default()
+        };
+        let error = try_part2_with("1\n0\n-3", &params).unwrap_err();
+        assert!(error.starts_with("day 20:"), "{}", error);
+    }
+
+    proptest! {
+        #[test]
+        fn mix_matches_oracle(input in input(Size::default())) {
+            let mut file = parse_input(&input, None).unwrap();
+            file.mix();
+            prop_assert_eq!(grove_coordinates(&file), oracle(&input, 1, 1));
+        }
+
+        #[test]
+        fn treap_mix_matches_oracle(input in input(Size::default())) {
+            let mut file = parse_input(&input, None).unwrap();
+            let mut list = TreapList::new(file.original_list.len());
+            file.mix_with(&mut list);
+            prop_assert_eq!(grove_coordinates(&file), oracle(&input, 1, 1));
+        }
+
+        #[test]
+        fn decrypted_mix_matches_oracle(input in input(Size { len: 20 })) {
+            let mut file = parse_input(&input, Some(DECRYPTION_KEY)).unwrap();
+            for _ in 1..=10 {
+                file.mix();
+            }
+            prop_assert_eq!(grove_coordinates(&file), oracle(&input, DECRYPTION_KEY, 10));
+        }
+
+        // the decryption key and ten rounds, against the original vec based mixing
+        #[test]
+        fn treap_part2_matches_original(input in input(Size::default())) {
+            prop_assert_eq!(part2_treap(&input), part2(&input));
+        }
+    }
 }
@@ -23,46 +23,79 @@ impl std::fmt::Display for MonkeyExpr {
     }
 }
 
+#[cfg(not(feature = "checked-arith"))]
+fn arith(left: i128, op: char, right: i128) -> Result<i128, String> {
+    Ok(match op {
+        '+' => left + right,
+        '-' => left - right,
+        '*' => left * right,
+        '/' => left / right,
+        _ => unreachable!(),
+    })
+}
+
+// besides overflow, a division that leaves a remainder is an error too:
+// the monkeys only ever divide evenly, so a remainder means a wrong answer
+#[cfg(feature = "checked-arith")]
+fn arith(left: i128, op: char, right: i128) -> Result<i128, String> {
+    let result = match op {
+        '+' => left.checked_add(right),
+        '-' => left.checked_sub(right),
+        '*' => left.checked_mul(right),
+        '/' => left
+            .checked_rem(right)
+            .filter(|&rem| rem == 0)
+            .and_then(|_| left.checked_div(right)),
+        _ => unreachable!(),
+    };
+    result.ok_or_else(|| {
+        format!(
+            "day 21: {} {} {} overflows i128 or doesn't divide evenly",
+            left, op, right
+        )
+    })
+}
+
 impl MonkeyExpr {
-    fn evaluate(&self, equalities: &HashMap<MonkeyExpr, MonkeyExpr>) -> MonkeyExpr {
-        match self {
+    fn evaluate(&self, equalities: &HashMap<MonkeyExpr, MonkeyExpr>) -> Result<MonkeyExpr, String> {
+        Ok(match self {
             MonkeyExpr::Value(value) => MonkeyExpr::Value(*value),
             MonkeyExpr::Add(left, right) => {
-                let left = left.evaluate(equalities);
-                let right = right.evaluate(equalities);
+                let left = left.evaluate(equalities)?;
+                let right = right.evaluate(equalities)?;
                 match (left, right) {
                     (MonkeyExpr::Value(left), MonkeyExpr::Value(right)) => {
-                        MonkeyExpr::Value(left + right)
+                        MonkeyExpr::Value(arith(left, '+', right)?)
                     }
                     (left, right) => MonkeyExpr::Add(Box::new(left), Box::new(right)),
                 }
             }
             MonkeyExpr::Sub(left, right) => {
-                let left = left.evaluate(equalities);
-                let right = right.evaluate(equalities);
+                let left = left.evaluate(equalities)?;
+                let right = right.evaluate(equalities)?;
                 match (left, right) {
                     (MonkeyExpr::Value(left), MonkeyExpr::Value(right)) => {
-                        MonkeyExpr::Value(left - right)
+                        MonkeyExpr::Value(arith(left, '-', right)?)
                     }
                     (left, right) => MonkeyExpr::Sub(Box::new(left), Box::new(right)),
                 }
             }
             MonkeyExpr::Mul(left, right) => {
-                let left = left.evaluate(equalities);
-                let right = right.evaluate(equalities);
+                let left = left.evaluate(equalities)?;
+                let right = right.evaluate(equalities)?;
                 match (left, right) {
                     (MonkeyExpr::Value(left), MonkeyExpr::Value(right)) => {
-                        MonkeyExpr::Value(left * right)
+                        MonkeyExpr::Value(arith(left, '*', right)?)
                     }
                     (left, right) => MonkeyExpr::Mul(Box::new(left), Box::new(right)),
                 }
             }
             MonkeyExpr::Div(left, right) => {
-                let left = left.evaluate(equalities);
-                let right = right.evaluate(equalities);
+                let left = left.evaluate(equalities)?;
+                let right = right.evaluate(equalities)?;
                 match (left, right) {
                     (MonkeyExpr::Value(left), MonkeyExpr::Value(right)) => {
-                        MonkeyExpr::Value(left / right)
+                        MonkeyExpr::Value(arith(left, '/', right)?)
                     }
                     (left, right) => MonkeyExpr::Div(Box::new(left), Box::new(right)),
                 }
@@ -70,17 +103,16 @@ impl MonkeyExpr {
             MonkeyExpr::Var(var) => {
                 let var = MonkeyExpr::Var(var.clone());
                 if let Some(value) = equalities.get(&var) {
-                    value.evaluate(equalities)
+                    value.evaluate(equalities)?
                 } else {
                     var
                 }
             }
-        }
+        })
     }
 }
 
-fn read_input(file: &str) -> HashMap<MonkeyExpr, MonkeyExpr> {
-    let input = std::fs::read_to_string(file).expect("Error reading input file!");
+fn parse_input(input: &str) -> HashMap<MonkeyExpr, MonkeyExpr> {
     input
         .lines()
         .map(|line| {
@@ -116,15 +148,25 @@ fn read_input(file: &str) -> HashMap<MonkeyExpr, MonkeyExpr> {
         .collect::<HashMap<_, _>>()
 }
 
-pub fn part1() {
-    let monkeys = read_input("input/day21.in");
+pub fn part1(input: &str) -> String {
+    try_part1(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// like `part1`, but an overflow found by the checked-arith feature is an error
+pub fn try_part1(input: &str) -> Result<String, String> {
+    let monkeys = parse_input(input);
     let root = MonkeyExpr::Var("root".to_string());
-    let root = root.evaluate(&monkeys);
-    println!("{:?}", root);
+    let root = root.evaluate(&monkeys)?;
+    Ok(root.to_string())
 }
 
-pub fn part2() {
-    let mut monkeys = read_input("input/day21.in");
+pub fn part2(input: &str) -> String {
+    try_part2(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// like `part2`, but an overflow found by the checked-arith feature is an error
+pub fn try_part2(input: &str) -> Result<String, String> {
+    let mut monkeys = parse_input(input);
     monkeys.remove(&MonkeyExpr::Var("humn".to_string()));
     let root = MonkeyExpr::Var("root".to_string());
     let expr = monkeys.get(&root).unwrap();
@@ -133,8 +175,8 @@ pub fn part2() {
         | MonkeyExpr::Sub(left, right)
         | MonkeyExpr::Mul(left, right)
         | MonkeyExpr::Div(left, right) => {
-            let left = left.evaluate(&monkeys.clone());
-            let right = right.evaluate(&monkeys.clone());
+            let left = left.evaluate(&monkeys)?;
+            let right = right.evaluate(&monkeys)?;
             (left, right)
         }
         _ => panic!("Unknown expr: {:?}", expr),
@@ -145,57 +187,55 @@ pub fn part2() {
         _ => panic!("Right shoule be fully evalueated: {:?}", right),
     };
 
-    println!("root: {} = {}", left, right_value);
-
     loop {
         match &left {
             MonkeyExpr::Add(inner_left, inner_right) => {
-                let inner_left = inner_left.evaluate(&monkeys.clone());
-                let inner_right = inner_right.evaluate(&monkeys.clone());
+                let inner_left = inner_left.evaluate(&monkeys)?;
+                let inner_right = inner_right.evaluate(&monkeys)?;
                 if let MonkeyExpr::Value(value) = inner_left {
-                    right_value -= value;
+                    right_value = arith(right_value, '-', value)?;
                     left = inner_right;
                 } else if let MonkeyExpr::Value(value) = inner_right {
-                    right_value -= value;
+                    right_value = arith(right_value, '-', value)?;
                     left = inner_left;
                 } else {
                     unreachable!();
                 }
             }
             MonkeyExpr::Sub(inner_left, inner_right) => {
-                let inner_left = inner_left.evaluate(&monkeys.clone());
-                let inner_right = inner_right.evaluate(&monkeys.clone());
+                let inner_left = inner_left.evaluate(&monkeys)?;
+                let inner_right = inner_right.evaluate(&monkeys)?;
                 if let MonkeyExpr::Value(value) = inner_left {
-                    right_value = value - right_value;
+                    right_value = arith(value, '-', right_value)?;
                     left = inner_right;
                 } else if let MonkeyExpr::Value(value) = inner_right {
-                    right_value += value;
+                    right_value = arith(right_value, '+', value)?;
                     left = inner_left;
                 } else {
                     unreachable!();
                 }
             }
             MonkeyExpr::Mul(inner_left, inner_right) => {
-                let inner_left = inner_left.evaluate(&monkeys.clone());
-                let inner_right = inner_right.evaluate(&monkeys.clone());
+                let inner_left = inner_left.evaluate(&monkeys)?;
+                let inner_right = inner_right.evaluate(&monkeys)?;
                 if let MonkeyExpr::Value(value) = inner_left {
-                    right_value /= value;
+                    right_value = arith(right_value, '/', value)?;
                     left = inner_right;
                 } else if let MonkeyExpr::Value(value) = inner_right {
-                    right_value /= value;
+                    right_value = arith(right_value, '/', value)?;
                     left = inner_left;
                 } else {
                     unreachable!();
                 }
             }
             MonkeyExpr::Div(inner_left, inner_right) => {
-                let inner_left = inner_left.evaluate(&monkeys.clone());
-                let inner_right = inner_right.evaluate(&monkeys.clone());
+                let inner_left = inner_left.evaluate(&monkeys)?;
+                let inner_right = inner_right.evaluate(&monkeys)?;
                 if let MonkeyExpr::Value(value) = inner_left {
-                    right_value = value / right_value;
+                    right_value = arith(value, '/', right_value)?;
                     left = inner_right;
                 } else if let MonkeyExpr::Value(value) = inner_right {
-                    right_value *= value;
+                    right_value = arith(right_value, '*', value)?;
                     left = inner_left;
                 } else {
                     unreachable!();
@@ -206,7 +246,43 @@ pub fn part2() {
             }
             _ => panic!("Unknown expr: {:?}", left),
         }
+    }
+
+    Ok(right_value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE), "152");
+        assert_eq!(part2(EXAMPLE), "301");
+    }
 
-        println!("{} = {}", left, right_value);
+    #[cfg(feature = "checked-arith")]
+    #[test]
+    fn overflow_is_an_error() {
+        let input = format!("root: a * a\na: {}", i128::MAX / 2);
+        let error = try_part1(&input).unwrap_err();
+        assert!(error.starts_with("day 21:"), "{}", error);
+        assert!(try_part1("root: a / b\na: 7\nb: 2").is_err());
     }
 }
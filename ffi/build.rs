@@ -0,0 +1,24 @@
+// generates aoc.h from the functions and constants in src/lib.rs into OUT_DIR.
+// the copy in include/ is only rewritten when AOC_UPDATE_HEADER is set, e.g.
+// `AOC_UPDATE_HEADER=1 cargo build`, and a test checks that it is up to date
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-env-changed=AOC_UPDATE_HEADER");
+    let bindings = cbindgen::Builder::new()
+        .with_config(cbindgen::Config {
+            usize_is_size_t: true,
+            ..Default::default()
+        })
+        .with_crate(&crate_dir)
+        .with_language(cbindgen::Language::C)
+        .with_include_guard("AOC_H")
+        .with_header("/* Generated by cbindgen from ffi/src/lib.rs, do not edit. */")
+        .generate()
+        .expect("Unable to generate bindings");
+    bindings.write_to_file(format!("{}/aoc.h", out_dir));
+    if std::env::var_os("AOC_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(format!("{}/include/aoc.h", crate_dir));
+    }
+}
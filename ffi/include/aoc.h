@@ -0,0 +1,66 @@
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit. */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The answer was written to the output buffer.
+ */
+#define AOC_OK 0
+
+/**
+ * There is no solver for that year, day and part.
+ */
+#define AOC_NOT_FOUND 1
+
+/**
+ * A pointer was null, or the input is not valid UTF-8.
+ */
+#define AOC_INVALID_ARGUMENT 2
+
+/**
+ * The output buffer is too small, `*out_len` is set to the length of the answer.
+ */
+#define AOC_BUFFER_TOO_SMALL 3
+
+/**
+ * The solver panicked, usually because the input was malformed.
+ */
+#define AOC_PANIC 4
+
+/**
+ * Solves one part of a puzzle.
+ *
+ * `input_ptr` points to `len` bytes of UTF-8 puzzle input, which does not need to be
+ * NUL terminated. The input is normalised the same way the runner does it, so CRLF line
+ * endings and trailing newlines are fine. `*out_len` is the size of `out_buf` in bytes.
+ * On `AOC_OK` the answer is written to `out_buf` followed by a NUL, and `*out_len` is set
+ * to the length of the answer without the NUL. On `AOC_BUFFER_TOO_SMALL` nothing is
+ * written and `*out_len` is set to the length of the answer, so the call can be repeated
+ * with a buffer of `*out_len + 1` bytes.
+ *
+ * # Safety
+ *
+ * `input_ptr` must be valid for reads of `len` bytes, `out_len` must be valid for reads
+ * and writes, and `out_buf` must be valid for writes of `*out_len` bytes.
+ */
+int32_t aoc_solve(int32_t year,
+                  uint32_t day,
+                  uint32_t part,
+                  const uint8_t *input_ptr,
+                  size_t len,
+                  uint8_t *out_buf,
+                  size_t *out_len);
+
+/**
+ * Returns 1 if there is a solver for that year, day and part, 0 otherwise.
+ */
+int32_t aoc_has_solver(int32_t year, uint32_t day, uint32_t part);
+
+#endif /* AOC_H */
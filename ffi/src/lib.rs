@@ -0,0 +1,144 @@
+// C API for the puzzle solvers.
+//
+// Every function catches panics from the solvers, so nothing unwinds into C code.
+// The header in include/aoc.h is generated from this file by build.rs.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::OnceLock;
+use std::{ptr, slice, str};
+
+use advent_of_code_2022::input::normalize;
+use advent_of_code_2022::registry::{Puzzle, Registry};
+
+/// The answer was written to the output buffer.
+pub const AOC_OK: i32 = 0;
+/// There is no solver for that year, day and part.
+pub const AOC_NOT_FOUND: i32 = 1;
+/// A pointer was null, or the input is not valid UTF-8.
+pub const AOC_INVALID_ARGUMENT: i32 = 2;
+/// The output buffer is too small, `*out_len` is set to the length of the answer.
+pub const AOC_BUFFER_TOO_SMALL: i32 = 3;
+/// The solver panicked, usually because the input was malformed.
+pub const AOC_PANIC: i32 = 4;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::with_all_years)
+}
+
+/// Solves one part of a puzzle.
+///
+/// `input_ptr` points to `len` bytes of UTF-8 puzzle input, which does not need to be
+/// NUL terminated. The input is normalised the same way the runner does it, so CRLF line
+/// endings and trailing newlines are fine. `*out_len` is the size of `out_buf` in bytes.
+/// On `AOC_OK` the answer is written to `out_buf` followed by a NUL, and `*out_len` is set
+/// to the length of the answer without the NUL. On `AOC_BUFFER_TOO_SMALL` nothing is
+/// written and `*out_len` is set to the length of the answer, so the call can be repeated
+/// with a buffer of `*out_len + 1` bytes.
+///
+/// # Safety
+///
+/// `input_ptr` must be valid for reads of `len` bytes, `out_len` must be valid for reads
+/// and writes, and `out_buf` must be valid for writes of `*out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: i32,
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if input_ptr.is_null() || out_len.is_null() || (out_buf.is_null() && *out_len > 0) {
+        return AOC_INVALID_ARGUMENT;
+    }
+    let Ok(input) = str::from_utf8(slice::from_raw_parts(input_ptr, len)) else {
+        return AOC_INVALID_ARGUMENT;
+    };
+    let Some(solver) = registry().solver(Puzzle::new(year, day, part)) else {
+        return AOC_NOT_FOUND;
+    };
+
+    let Ok(answer) = panic::catch_unwind(AssertUnwindSafe(|| solver(&normalize(input)))) else {
+        return AOC_PANIC;
+    };
+
+    let capacity = *out_len;
+    *out_len = answer.len();
+    if answer.len() + 1 > capacity {
+        return AOC_BUFFER_TOO_SMALL;
+    }
+    ptr::copy_nonoverlapping(answer.as_ptr(), out_buf, answer.len());
+    *out_buf.add(answer.len()) = 0;
+    AOC_OK
+}
+
+/// Returns 1 if there is a solver for that year, day and part, 0 otherwise.
+#[no_mangle]
+pub extern "C" fn aoc_has_solver(year: i32, day: u32, part: u32) -> i32 {
+    panic::catch_unwind(|| registry().solver(Puzzle::new(year, day, part)).is_some())
+        .map_or(0, |found| found as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(year: i32, day: u32, part: u32, input: &str, capacity: usize) -> (i32, String, usize) {
+        let mut buf = vec![0u8; capacity];
+        let mut len = capacity;
+        let status = unsafe {
+            aoc_solve(
+                year,
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                buf.as_mut_ptr(),
+                &mut len,
+            )
+        };
+        let answer = String::from_utf8_lossy(&buf[..len.min(capacity)]).to_string();
+        (status, answer, len)
+    }
+
+    #[test]
+    fn solves_example() {
+        let (status, answer, len) = solve(2022, 2, 1, "A Y\nB X\nC Z", 64);
+        assert_eq!(status, AOC_OK);
+        assert_eq!(answer, "15");
+        assert_eq!(len, 2);
+    }
+
+    #[test]
+    fn reports_required_length() {
+        let (status, _, len) = solve(2022, 2, 1, "A Y\nB X\nC Z", 2);
+        assert_eq!(status, AOC_BUFFER_TOO_SMALL);
+        assert_eq!(len, 2);
+    }
+
+    #[test]
+    fn catches_panics() {
+        let (status, _, _) = solve(2022, 2, 1, "not a strategy guide", 64);
+        assert_eq!(status, AOC_PANIC);
+    }
+
+    #[test]
+    fn unknown_puzzle() {
+        let (status, _, _) = solve(2015, 1, 1, "", 64);
+        assert_eq!(status, AOC_NOT_FOUND);
+        assert_eq!(aoc_has_solver(2015, 1, 1), 0);
+        assert_eq!(aoc_has_solver(2022, 1, 1), 1);
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc.h"));
+        let committed = include_str!("../include/aoc.h");
+        assert!(
+            generated == committed,
+            "include/aoc.h is out of date, run `AOC_UPDATE_HEADER=1 cargo build` in ffi/"
+        );
+    }
+}
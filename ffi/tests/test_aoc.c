@@ -0,0 +1,56 @@
+/*
+ * Exercises the C API on a few of the puzzle examples.
+ * Build and run with `make test` in the ffi directory.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void expect(int year, int day, int part, const char *input, int status,
+                   const char *answer) {
+    char buf[64];
+    size_t len = sizeof(buf);
+    int got = aoc_solve(year, day, part, (const uint8_t *)input, strlen(input),
+                        (uint8_t *)buf, &len);
+    if (got != status || (status == AOC_OK && strcmp(buf, answer) != 0)) {
+        printf("FAIL %d day %d part %d: status %d answer '%s', expected %d '%s'\n",
+               year, day, part, got, got == AOC_OK ? buf : "", status, answer);
+        failures++;
+    } else {
+        printf("ok   %d day %d part %d\n", year, day, part);
+    }
+}
+
+int main(void) {
+    const char *calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+    const char *strategy = "A Y\nB X\nC Z";
+    const char *signal = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    expect(2022, 1, 1, calories, AOC_OK, "24000");
+    expect(2022, 1, 2, calories, AOC_OK, "45000");
+    expect(2022, 2, 1, strategy, AOC_OK, "15");
+    expect(2022, 2, 2, strategy, AOC_OK, "12");
+    expect(2022, 6, 1, signal, AOC_OK, "7");
+    expect(2022, 6, 2, signal, AOC_OK, "19");
+
+    /* malformed input panics inside the solver, which must not reach us */
+    expect(2022, 1, 1, "not a number", AOC_PANIC, "");
+    expect(2015, 1, 1, "", AOC_NOT_FOUND, "");
+
+    /* too small a buffer reports the length that is needed */
+    char small[2];
+    size_t len = sizeof(small);
+    int status = aoc_solve(2022, 1, 1, (const uint8_t *)calories, strlen(calories),
+                           (uint8_t *)small, &len);
+    if (status != AOC_BUFFER_TOO_SMALL || len != 5) {
+        printf("FAIL small buffer: status %d len %zu\n", status, len);
+        failures++;
+    } else {
+        printf("ok   small buffer\n");
+    }
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,119 @@
+use proptest::prelude::*;
+
+// the most stacks, crates in a stack and moves in an input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub stacks: usize,
+    pub height: usize,
+    pub moves: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            stacks: 9,
+            height: 8,
+            moves: 30,
+        }
+    }
+}
+
+// random crate layouts followed by moves that are valid for them,
+// stacks are allowed to be empty both before and after the moves
+pub fn input(size: Size) -> impl Strategy<Value = String> {
+    (1..=size.stacks)
+        .prop_flat_map(move |n| {
+            (
+                prop::collection::vec(
+                    prop::collection::vec(prop::char::range('A', 'Z'), 0..=size.height),
+                    n,
+                ),
+                prop::collection::vec(any::<(usize, usize, usize)>(), 0..=size.moves),
+            )
+        })
+        .prop_map(|(stacks, picks)| render_input(stacks, picks))
+}
+
+fn render_input(stacks: Vec<Vec<char>>, picks: Vec<(usize, usize, usize)>) -> String {
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    let mut s = String::new();
+    for y in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(y) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_string(),
+            })
+            .collect();
+        s.push_str(&row.join(" "));
+        s.push('\n');
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|i| format!(" {} ", i)).collect();
+    s.push_str(&numbers.join(" "));
+    s.push_str("\n\n");
+
+    // only pick moves that take crates from a non-empty stack
+    let mut sizes: Vec<usize> = stacks.iter().map(|s| s.len()).collect();
+    for (from, to, amount) in picks {
+        let non_empty: Vec<usize> = (0..sizes.len()).filter(|i| sizes[*i] > 0).collect();
+        if non_empty.is_empty() || sizes.len() < 2 {
+            break;
+        }
+        let from = non_empty[from % non_empty.len()];
+        let to = (from + 1 + to % (sizes.len() - 1)) % sizes.len();
+        let amount = 1 + amount % sizes[from];
+        sizes[from] -= amount;
+        sizes[to] += amount;
+        s.push_str(&format!(
+            "move {} from {} to {}\n",
+            amount,
+            from + 1,
+            to + 1
+        ));
+    }
+    s
+}
+
+// amount, from, to
+type Move = (usize, usize, usize);
+
+// reads the crates by column position under each stack number
+fn oracle_parse(input: &str) -> (Vec<Vec<char>>, Vec<Move>) {
+    let (drawing, moves) = input.split_once("\n\n").unwrap();
+    let mut rows: Vec<&str> = drawing.lines().collect();
+    let numbers = rows.pop().unwrap();
+    let mut stacks = Vec::new();
+    for (col, c) in numbers.char_indices() {
+        if !c.is_ascii_digit() {
+            continue;
+        }
+        let stack: Vec<char> = rows
+            .iter()
+            .rev()
+            .filter_map(|row| row.chars().nth(col))
+            .filter(|c| c.is_ascii_uppercase())
+            .collect();
+        stacks.push(stack);
+    }
+    let moves = moves
+        .lines()
+        .map(|line| {
+            let n: Vec<usize> = line.split(' ').filter_map(|w| w.parse().ok()).collect();
+            (n[0], n[1] - 1, n[2] - 1)
+        })
+        .collect();
+    (stacks, moves)
+}
+
+pub fn oracle(input: &str, keep_order: bool) -> String {
+    let (mut stacks, moves) = oracle_parse(input);
+    for (amount, from, to) in moves {
+        let at = stacks[from].len() - amount;
+        let mut moved = stacks[from].split_off(at);
+        if !keep_order {
+            moved.reverse();
+        }
+        stacks[to].extend(moved);
+    }
+    stacks.iter().filter_map(|s| s.last()).collect()
+}
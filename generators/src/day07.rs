@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+
+// the puzzle's disk, and the free space the update needs on it
+pub const DISK_SPACE: usize = 70_000_000;
+pub const UPDATE_SPACE: usize = 30_000_000;
+
+// the most directories and files in a tree, the most extra visits to them,
+// and the largest file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub dirs: usize,
+    pub files: usize,
+    pub visits: usize,
+    pub file_size: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            dirs: 20,
+            files: 30,
+            visits: 10,
+            file_size: 300000,
+        }
+    }
+}
+
+// a random directory tree, given as the parent of each directory
+// (directory 0 is the root) and the files in each directory
+#[derive(Debug, Clone)]
+struct Tree {
+    parents: Vec<usize>,
+    files: Vec<(usize, usize)>,
+}
+
+impl Tree {
+    fn path(&self, mut dir: usize) -> Vec<usize> {
+        let mut path = vec![dir];
+        while dir != 0 {
+            dir = self.parents[dir - 1];
+            path.push(dir);
+        }
+        path.reverse();
+        path
+    }
+}
+
+fn tree(size: Size) -> impl Strategy<Value = Tree> {
+    prop::collection::vec(any::<usize>(), 0..size.dirs).prop_flat_map(move |picks| {
+        // every directory gets a parent that was created before it
+        let parents: Vec<usize> = picks
+            .iter()
+            .enumerate()
+            .map(|(i, pick)| pick % (i + 1))
+            .collect();
+        let num_dirs = parents.len() + 1;
+        prop::collection::vec((0..num_dirs, 1..size.file_size), 0..size.files).prop_map(
+            move |files| Tree {
+                parents: parents.clone(),
+                files,
+            },
+        )
+    })
+}
+
+// a terminal session that explores the tree, visiting directories in the given
+// order. directories can be visited (and listed) any number of times
+pub fn input(size: Size) -> impl Strategy<Value = String> {
+    tree(size)
+        .prop_flat_map(move |tree| {
+            let num_dirs = tree.parents.len() + 1;
+            (
+                Just(tree),
+                prop::collection::vec(0..num_dirs, 0..size.visits),
+                any::<bool>(),
+            )
+        })
+        .prop_map(|(tree, mut visits, shortcut)| {
+            // visit every directory at least once, so the whole tree is seen
+            visits.extend(0..tree.parents.len() + 1);
+            render_session(&tree, &visits, shortcut)
+        })
+}
+
+fn render_session(tree: &Tree, visits: &[usize], shortcut: bool) -> String {
+    let mut s = String::from("$ cd /\n");
+    let mut current = vec![0];
+    for &dir in visits {
+        let target = tree.path(dir);
+        let common = current
+            .iter()
+            .zip(target.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        if shortcut && common < current.len() {
+            s.push_str("$ cd /\n");
+            current.truncate(1);
+        }
+        while current.len() > common.max(1) {
+            s.push_str("$ cd ..\n");
+            current.pop();
+        }
+        for d in &target[current.len()..] {
+            s.push_str(&format!("$ cd d{}\n", d));
+            current.push(*d);
+        }
+        s.push_str("$ ls\n");
+        for (child, parent) in tree.parents.iter().enumerate() {
+            if *parent == dir {
+                s.push_str(&format!("dir d{}\n", child + 1));
+            }
+        }
+        for (i, (file_dir, size)) in tree.files.iter().enumerate() {
+            if *file_dir == dir {
+                s.push_str(&format!("{} f{}.txt\n", size, i));
+            }
+        }
+    }
+    s
+}
+
+// replays the session keeping track of full paths,
+// then adds up the files below each directory one by one
+pub fn oracle_sizes(input: &str) -> Vec<usize> {
+    let mut cwd: Vec<String> = Vec::new();
+    let mut dirs: Vec<Vec<String>> = vec![Vec::new()];
+    let mut files: HashMap<Vec<String>, usize> = HashMap::new();
+    for line in input.lines() {
+        let tokens: Vec<&str> = line.split(' ').collect();
+        match tokens[..] {
+            ["$", "cd", "/"] => cwd.clear(),
+            ["$", "cd", ".."] => {
+                cwd.pop();
+            }
+            ["$", "cd", dir] => {
+                cwd.push(dir.to_string());
+                if !dirs.contains(&cwd) {
+                    dirs.push(cwd.clone());
+                }
+            }
+            ["$", "ls"] | ["dir", _] => {}
+            [size, name] => {
+                let mut path = cwd.clone();
+                path.push(name.to_string());
+                files.insert(path, size.parse().unwrap());
+            }
+            _ => panic!("Invalid line {}", line),
+        }
+    }
+    dirs.iter()
+        .map(|dir| {
+            files
+                .iter()
+                .filter(|(path, _)| path.starts_with(dir))
+                .map(|(_, size)| size)
+                .sum()
+        })
+        .collect()
+}
+
+// part 2 the slow way: tries deleting each directory, smallest first, until the
+// update fits in what's free afterwards. None if the files don't fit on the disk
+pub fn oracle_to_delete(input: &str) -> Option<usize> {
+    let mut sizes = oracle_sizes(input);
+    let used = sizes[0];
+    if used > DISK_SPACE {
+        return None;
+    }
+    sizes.sort();
+    sizes
+        .into_iter()
+        .find(|size| DISK_SPACE - (used - size) >= UPDATE_SPACE)
+}
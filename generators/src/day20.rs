@@ -0,0 +1,61 @@
+use proptest::prelude::*;
+
+// the longest list in an input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub len: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size { len: 50 }
+    }
+}
+
+// a list with exactly one zero and plenty of duplicates,
+// mixing small numbers with ones much larger than the list
+pub fn input(size: Size) -> impl Strategy<Value = String> {
+    let number = prop_oneof![
+        3 => (-5i64..=5).prop_filter("zero is placed separately", |n| *n != 0),
+        1 => (-100_000i64..=100_000).prop_filter("zero is placed separately", |n| *n != 0),
+    ];
+    (prop::collection::vec(number, 1..size.len), any::<usize>()).prop_map(|(mut numbers, zero)| {
+        numbers.insert(zero % (numbers.len() + 1), 0);
+        numbers
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+// mixes by swapping each number with its neighbour one step at a time
+pub fn oracle(input: &str, decryption_key: i64, rounds: usize) -> i64 {
+    let numbers: Vec<i64> = input
+        .lines()
+        .map(|line| line.parse::<i64>().unwrap() * decryption_key)
+        .collect();
+    let len = numbers.len();
+    let mut list: Vec<(usize, i64)> = numbers.iter().copied().enumerate().collect();
+    for _ in 0..rounds {
+        for (i, number) in numbers.iter().enumerate() {
+            let mut pos = list.iter().position(|(j, _)| *j == i).unwrap();
+            // moving len - 1 steps brings a number back to where it started
+            let steps = number.unsigned_abs() % (len as u64 - 1).max(1);
+            for _ in 0..steps {
+                let next = if *number > 0 {
+                    (pos + 1) % len
+                } else {
+                    (pos + len - 1) % len
+                };
+                list.swap(pos, next);
+                pos = next;
+            }
+        }
+    }
+    let zero = list.iter().position(|(_, n)| *n == 0).unwrap();
+    [1000, 2000, 3000]
+        .iter()
+        .map(|i| list[(zero + i) % len].1)
+        .sum()
+}
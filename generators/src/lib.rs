@@ -0,0 +1,60 @@
+// Random puzzle inputs of a chosen size and slow but obviously right solvers to check
+// the real ones against. The generators are proptest strategies, so the day crates'
+// property tests shrink a failing input, and `sample` turns one into a single input
+// for a benchmark.
+
+use proptest::strategy::{Strategy, ValueTree};
+use proptest::test_runner::TestRunner;
+
+pub mod day05;
+pub mod day07;
+pub mod day20;
+
+// one input from `strategy`, the same one every time
+pub fn sample<S: Strategy>(strategy: S) -> S::Value {
+    let mut runner = TestRunner::deterministic();
+    strategy
+        .new_tree(&mut runner)
+        .unwrap_or_else(|e| panic!("Error generating an input: {}", e))
+        .current()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_are_kept() {
+        let size = day20::Size { len: 5 };
+        let input = sample(day20::input(size));
+        assert_eq!(input, sample(day20::input(size)));
+        assert!(input.lines().count() <= 5);
+        assert_eq!(input.lines().filter(|n| *n == "0").count(), 1);
+
+        let size = day05::Size {
+            stacks: 3,
+            height: 2,
+            moves: 4,
+        };
+        let input = sample(day05::input(size));
+        let (drawing, moves) = input.split_once("\n\n").unwrap();
+        assert!(drawing.lines().count() <= 3);
+        assert!(moves.lines().count() <= 4);
+    }
+
+    #[test]
+    fn oracles_solve_the_examples() {
+        let crates = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+                      move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n";
+        assert_eq!(day05::oracle(crates, false), "CMZ");
+        assert_eq!(day05::oracle(crates, true), "MCD");
+
+        let numbers = "1\n2\n-3\n3\n-2\n0\n4";
+        assert_eq!(day20::oracle(numbers, 1, 1), 3);
+        assert_eq!(day20::oracle(numbers, 811589153, 10), 1623178306);
+
+        let session = "$ cd /\n$ ls\ndir a\n10 b.txt\n$ cd a\n$ ls\n20 c.txt";
+        assert_eq!(day07::oracle_sizes(session), vec![30, 20]);
+        assert_eq!(day07::oracle_to_delete(session), Some(20));
+    }
+}
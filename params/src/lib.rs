@@ -0,0 +1,118 @@
+// Puzzle parameters that are fixed by the puzzle text, but differ for the examples,
+// like the row to check in day 15. A day declares them with `params!`
+// and the runner sets them by name from the command line or a `.params` file.
+
+pub trait Params: Default {
+    // parses `value` into the field called `name`
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    // every field as (name, current value)
+    fn values(&self) -> Vec<(&'static str, String)>;
+}
+
+// the defaults with the overrides applied in order, so later ones win
+pub fn with_overrides<P: Params>(overrides: &[(String, String)]) -> Result<P, String> {
+    let mut params = P::default();
+    for (name, value) in overrides {
+        params.set(name, value)?;
+    }
+    Ok(params)
+}
+
+// the names and default values of a day's parameters
+pub fn defaults<P: Params>() -> Vec<(&'static str, String)> {
+    P::default().values()
+}
+
+// reads `name = value` lines, blank lines and lines starting with # are skipped
+pub fn parse_file(contents: &str) -> Result<Vec<(String, String)>, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_override)
+        .collect()
+}
+
+// "name=value" -> (name, value)
+pub fn parse_override(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+        None => Err(format!("Expected name=value, got {}", arg)),
+    }
+}
+
+// declares a struct of parameters with their default values, e.g.
+// params! {
+//     pub struct Params {
+//         row: i64 = 2000000,
+//     }
+// }
+#[macro_export]
+macro_rules! params {
+    (pub struct $name:ident { $($field:ident: $ty:ty = $default:expr,)* }) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $(pub $field: $ty,)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $crate::Params for $name {
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(stringify!($field) => {
+                        self.$field = value.parse().map_err(|_| {
+                            format!("{} must be a {}, got {}", name, stringify!($ty), value)
+                        })?;
+                    })*
+                    _ => return Err(format!("Unknown parameter {}", name)),
+                }
+                Ok(())
+            }
+
+            fn values(&self) -> Vec<(&'static str, String)> {
+                vec![$((stringify!($field), self.$field.to_string()),)*]
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        pub struct Example {
+            row: i64 = 2000000,
+            rounds: usize = 10,
+        }
+    }
+
+    #[test]
+    fn overrides() {
+        let overrides = parse_file("# the example\nrow = 10\n\nrounds=3\nrow=11").unwrap();
+        let params: Example = with_overrides(&overrides).unwrap();
+        assert_eq!(params, Example { row: 11, rounds: 3 });
+        assert_eq!(
+            defaults::<Example>(),
+            vec![("row", "2000000".to_string()), ("rounds", "10".to_string())]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let bad = |name: &str, value: &str| {
+            with_overrides::<Example>(&[(name.to_string(), value.to_string())]).unwrap_err()
+        };
+        assert_eq!(bad("rounds", "-1"), "rounds must be a usize, got -1");
+        assert_eq!(bad("columns", "1"), "Unknown parameter columns");
+        assert!(parse_override("row").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+// Days built as dynamic libraries, so a new implementation can be tried
+// without relinking the runner. A plugin crate is a cdylib that calls
+//   plugin::export_day!(2022, 5, day05::part1, day05::part2);
+// and the runner loads every plugin in a directory with `load_dir`.
+//
+// Everything that crosses the library boundary uses the C ABI, and the plugin reports
+// which version of it was built against before anything else is called.
+
+use std::ffi::c_void;
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::{slice, str};
+
+// bump this whenever `PluginDay` or the exported functions change
+pub const ABI_VERSION: u32 = 1;
+
+// status codes returned by `PluginDay::solve`
+pub const SOLVED: i32 = 0;
+pub const NO_SUCH_PART: i32 = 1;
+pub const INVALID_INPUT: i32 = 2;
+pub const PANICKED: i32 = 3;
+
+// called by the plugin with the answer, `ctx` is passed back untouched
+pub type WriteAnswer = unsafe extern "C" fn(ctx: *mut c_void, answer: *const u8, len: usize);
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PluginDay {
+    pub year: i32,
+    pub day: u32,
+    pub solve: unsafe extern "C" fn(
+        part: u32,
+        input: *const u8,
+        len: usize,
+        write: WriteAnswer,
+        ctx: *mut c_void,
+    ) -> i32,
+}
+
+// exports a day's solvers from a cdylib
+#[macro_export]
+macro_rules! export_day {
+    ($year:expr, $day:expr, $part1:path, $part2:path) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_abi_version() -> u32 {
+            $crate::ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_day() -> $crate::PluginDay {
+            unsafe extern "C" fn solve(
+                part: u32,
+                input: *const u8,
+                len: usize,
+                write: $crate::WriteAnswer,
+                ctx: *mut ::std::ffi::c_void,
+            ) -> i32 {
+                $crate::solve(
+                    &[$part1 as fn(&str) -> String, $part2],
+                    part,
+                    input,
+                    len,
+                    write,
+                    ctx,
+                )
+            }
+            $crate::PluginDay {
+                year: $year,
+                day: $day,
+                solve,
+            }
+        }
+    };
+}
+
+// the plugin side of `PluginDay::solve`, never lets a panic cross into the runner
+//
+// # Safety
+//
+// `input` must be valid for reads of `len` bytes
+#[doc(hidden)]
+pub unsafe fn solve(
+    parts: &[fn(&str) -> String],
+    part: u32,
+    input: *const u8,
+    len: usize,
+    write: WriteAnswer,
+    ctx: *mut c_void,
+) -> i32 {
+    let Some(solver) = (part as usize).checked_sub(1).and_then(|i| parts.get(i)) else {
+        return NO_SUCH_PART;
+    };
+    let Ok(input) = str::from_utf8(slice::from_raw_parts(input, len)) else {
+        return INVALID_INPUT;
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| solver(input))) {
+        Ok(answer) => {
+            write(ctx, answer.as_ptr(), answer.len());
+            SOLVED
+        }
+        Err(_) => PANICKED,
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Load(PathBuf, libloading::Error),
+    // (path, the plugin's version)
+    AbiMismatch(PathBuf, u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Load(path, e) => write!(f, "Can't load plugin {}: {}", path.display(), e),
+            Error::AbiMismatch(path, version) => write!(
+                f,
+                "Plugin {} was built for ABI version {}, but the runner uses version {}, rebuild it",
+                path.display(),
+                version,
+                ABI_VERSION
+            ),
+        }
+    }
+}
+
+// a loaded plugin, the library stays loaded for as long as this lives
+pub struct Plugin {
+    path: PathBuf,
+    day: PluginDay,
+    _library: libloading::Library,
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Plugin, Error> {
+        let load_error = |e| Error::Load(path.to_path_buf(), e);
+        // Safety: loading a library runs its initialisers, so only load plugins you built.
+        // the ABI version is checked before `aoc_plugin_day` is called
+        unsafe {
+            let library = libloading::Library::new(path).map_err(load_error)?;
+            let version: libloading::Symbol<extern "C" fn() -> u32> =
+                library.get(b"aoc_plugin_abi_version").map_err(load_error)?;
+            let version = version();
+            if version != ABI_VERSION {
+                return Err(Error::AbiMismatch(path.to_path_buf(), version));
+            }
+            let day: libloading::Symbol<extern "C" fn() -> PluginDay> =
+                library.get(b"aoc_plugin_day").map_err(load_error)?;
+            let day = day();
+            Ok(Plugin {
+                path: path.to_path_buf(),
+                day,
+                _library: library,
+            })
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.day.year
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day.day
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn solve(&self, part: u32, input: &str) -> Result<String, String> {
+        unsafe extern "C" fn write(ctx: *mut c_void, answer: *const u8, len: usize) {
+            let out = &mut *(ctx as *mut Vec<u8>);
+            out.extend_from_slice(slice::from_raw_parts(answer, len));
+        }
+
+        let mut answer: Vec<u8> = Vec::new();
+        let status = unsafe {
+            (self.day.solve)(
+                part,
+                input.as_ptr(),
+                input.len(),
+                write,
+                &mut answer as *mut Vec<u8> as *mut c_void,
+            )
+        };
+        let name = self.path.display();
+        match status {
+            SOLVED => String::from_utf8(answer)
+                .map_err(|_| format!("{} gave an answer that isn't UTF-8", name)),
+            NO_SUCH_PART => Err(format!("{} has no part {}", name, part)),
+            INVALID_INPUT => Err(format!("{} can't read the input", name)),
+            PANICKED => Err(format!(
+                "{} panicked, the input is probably malformed",
+                name
+            )),
+            _ => Err(format!("{} returned unknown status {}", name, status)),
+        }
+    }
+}
+
+// every dynamic library in `dir`, in file name order.
+// each one either loads or says why it didn't, so one bad plugin doesn't stop the others
+pub fn load_dir(dir: &Path) -> Result<Vec<Result<Plugin, Error>>, std::io::Error> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+    Ok(paths.iter().map(|path| Plugin::load(path)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe extern "C" fn collect(ctx: *mut c_void, answer: *const u8, len: usize) {
+        let out = &mut *(ctx as *mut Vec<u8>);
+        out.extend_from_slice(slice::from_raw_parts(answer, len));
+    }
+
+    fn call(part: u32, input: &str) -> (i32, String) {
+        let parts: [fn(&str) -> String; 2] = [
+            |input| input.len().to_string(),
+            |_| panic!("not implemented"),
+        ];
+        let mut out: Vec<u8> = Vec::new();
+        let status = unsafe {
+            solve(
+                &parts,
+                part,
+                input.as_ptr(),
+                input.len(),
+                collect,
+                &mut out as *mut Vec<u8> as *mut c_void,
+            )
+        };
+        (status, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn statuses() {
+        assert_eq!(call(1, "abc"), (SOLVED, "3".to_string()));
+        assert_eq!(call(2, "abc").0, PANICKED);
+        assert_eq!(call(0, "abc").0, NO_SUCH_PART);
+        assert_eq!(call(3, "abc").0, NO_SUCH_PART);
+    }
+}
@@ -0,0 +1 @@
+plugin::export_day!(2022, 1, day01::part1, day01::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 2, day02::part1, day02::part2);
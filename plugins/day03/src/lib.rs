@@ -0,0 +1 @@
+plugin::export_day!(2022, 3, day03::part1, day03::part2);
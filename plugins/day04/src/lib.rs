@@ -0,0 +1 @@
+plugin::export_day!(2022, 4, day04::part1, day04::part2);
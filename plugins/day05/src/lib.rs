@@ -0,0 +1 @@
+plugin::export_day!(2022, 5, day05::part1, day05::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 6, day06::part1, day06::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 7, day07::part1, day07::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 8, day08::part1, day08::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 9, day09::part1, day09::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 10, day10::part1, day10::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 11, day11::part1, day11::part2);
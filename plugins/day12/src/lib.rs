@@ -0,0 +1 @@
+plugin::export_day!(2022, 12, day12::part1, day12::part2);
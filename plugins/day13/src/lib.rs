@@ -0,0 +1 @@
+plugin::export_day!(2022, 13, day13::part1, day13::part2);
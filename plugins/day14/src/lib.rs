@@ -0,0 +1 @@
+plugin::export_day!(2022, 14, day14::part1, day14::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 15, day15::part1, day15::part2);
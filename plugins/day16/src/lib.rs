@@ -0,0 +1 @@
+plugin::export_day!(2022, 16, day16::part1, day16::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 17, day17::part1, day17::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 18, day18::part1, day18::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 19, day19::part1, day19::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 20, day20::part1, day20::part2);
@@ -0,0 +1 @@
+plugin::export_day!(2022, 21, day21::part1, day21::part2);
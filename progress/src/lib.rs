@@ -0,0 +1,125 @@
+// Lets a long running solver say how far along it is, and lets whoever started it
+// ask it to stop. The solver reports through `set`/`advance` and checks `check`
+// now and then, which returns `Err(Cancelled)` once `cancel` has been called.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+// why a solver that reports its progress has no answer: it was cancelled,
+// or it found something it can't go on from, like an overflow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stopped {
+    Cancelled,
+    Failed(String),
+}
+
+impl From<Cancelled> for Stopped {
+    fn from(_: Cancelled) -> Self {
+        Stopped::Cancelled
+    }
+}
+
+impl fmt::Display for Stopped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stopped::Cancelled => write!(f, "cancelled"),
+            Stopped::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Stopped {}
+
+// cheap to clone, every clone reports to and cancels the same run
+#[derive(Clone, Default)]
+pub struct Progress {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    label: Mutex<String>,
+    done: AtomicU64,
+    total: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Progress::default()
+    }
+
+    // starts a new stage of work with `total` steps
+    pub fn start(&self, label: &str, total: u64) {
+        *self.inner.label.lock().unwrap() = label.to_string();
+        self.inner.done.store(0, Ordering::Relaxed);
+        self.inner.total.store(total, Ordering::Relaxed);
+    }
+
+    pub fn set(&self, done: u64) {
+        self.inner.done.store(done, Ordering::Relaxed);
+    }
+
+    // safe to call from several threads at once
+    pub fn advance(&self, steps: u64) {
+        self.inner.done.fetch_add(steps, Ordering::Relaxed);
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    // for solvers to use with `?`
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    // (label, done, total)
+    pub fn get(&self) -> (String, u64, u64) {
+        (
+            self.inner.label.lock().unwrap().clone(),
+            self.inner.done.load(Ordering::Relaxed),
+            self.inner.total.load(Ordering::Relaxed),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_state() {
+        let progress = Progress::new();
+        let solver = progress.clone();
+        solver.start("rows", 10);
+        solver.advance(3);
+        solver.advance(4);
+        assert_eq!(progress.get(), ("rows".to_string(), 7, 10));
+
+        assert_eq!(solver.check(), Ok(()));
+        progress.cancel();
+        assert_eq!(solver.check(), Err(Cancelled));
+        assert_eq!(Stopped::from(Cancelled), Stopped::Cancelled);
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::registry::Puzzle;
+
+// Answers of earlier runs, one JSON object per line. A later line for the same key
+// replaces an earlier one, so the file is only ever appended to.
+pub const CACHE_PATH: &str = "cache.jsonl";
+
+// an answer is only reused for the same solver on the same input,
+// built from the same sources of the day
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub puzzle: Puzzle,
+    pub variant: String,
+    pub source: u64,
+    pub input: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub answer: String,
+    pub time: Duration,
+    pub memory: usize,
+}
+
+pub struct Cache {
+    path: String,
+    entries: HashMap<Key, Entry>,
+}
+
+impl Cache {
+    // a missing file is an empty cache, lines that can't be read are skipped
+    pub fn open(path: &str) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            contents => contents?,
+        };
+        let entries = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .filter_map(|value| from_json(&value))
+            .collect();
+        Ok(Cache {
+            path: path.to_string(),
+            entries,
+        })
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, key: Key, entry: Entry) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", to_json(&key, &entry))?;
+        self.entries.insert(key, entry);
+        Ok(())
+    }
+}
+
+// 64 bit FNV-1a of the input, the same on every machine and Rust version
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// the hashes are written as hex strings, JSON numbers can't hold all of a u64 everywhere
+fn to_json(key: &Key, entry: &Entry) -> Value {
+    json!({
+        "year": key.puzzle.year,
+        "day": key.puzzle.day,
+        "part": key.puzzle.part,
+        "variant": key.variant,
+        "source": format!("{:016x}", key.source),
+        "input": format!("{:016x}", key.input),
+        "answer": entry.answer,
+        "time_ns": entry.time.as_nanos() as u64,
+        "memory": entry.memory,
+    })
+}
+
+fn from_json(value: &Value) -> Option<(Key, Entry)> {
+    let hex = |name: &str| u64::from_str_radix(value[name].as_str()?, 16).ok();
+    let key = Key {
+        puzzle: Puzzle::new(
+            value["year"].as_i64()? as i32,
+            value["day"].as_u64()? as u32,
+            value["part"].as_u64()? as u32,
+        ),
+        variant: value["variant"].as_str()?.to_string(),
+        source: hex("source")?,
+        input: hex("input")?,
+    };
+    let entry = Entry {
+        answer: value["answer"].as_str()?.to_string(),
+        time: Duration::from_nanos(value["time_ns"].as_u64()?),
+        memory: value["memory"].as_u64()? as usize,
+    };
+    Some((key, entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-cache-{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap();
+        let key = Key {
+            puzzle: Puzzle::new(2022, 16, 2),
+            variant: "default".to_string(),
+            source: u64::MAX,
+            input: hash("AA"),
+        };
+        let entry = Entry {
+            answer: "2911".to_string(),
+            time: Duration::from_millis(1500),
+            memory: 1024,
+        };
+
+        let mut cache = Cache::open(path).unwrap();
+        cache.insert(key.clone(), entry.clone()).unwrap();
+        let reopened = Cache::open(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(reopened.get(&key), Some(&entry));
+        let other_input = Key {
+            input: hash("BB"),
+            ..key
+        };
+        assert_eq!(reopened.get(&other_input), None);
+    }
+}
@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::registry::Puzzle;
+
+// Every solve on the real input is appended here as one JSON object per line,
+// so the file can be kept around across commits and only ever grows.
+pub const HISTORY_PATH: &str = "history.jsonl";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub revision: String,
+    pub machine: String,
+    // how the solvers were built, only runs built the same way are compared
+    pub profile: String,
+    pub features: Vec<String>,
+    // seconds since the unix epoch
+    pub timestamp: u64,
+    pub puzzle: Puzzle,
+    pub answer: String,
+    pub time: Duration,
+    // peak heap bytes while solving
+    pub memory: usize,
+}
+
+impl Record {
+    // a record for this build and machine, `revision` is from `revision()`
+    // which asks git, so it's looked up once for all the records of a run
+    pub fn new(
+        revision: &str,
+        puzzle: Puzzle,
+        answer: &str,
+        time: Duration,
+        memory: usize,
+    ) -> Self {
+        Record {
+            revision: revision.to_string(),
+            machine: machine(),
+            profile: profile().to_string(),
+            features: features(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            puzzle,
+            answer: answer.to_string(),
+            time,
+            memory,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "revision": self.revision,
+            "machine": self.machine,
+            "profile": self.profile,
+            "features": self.features,
+            "timestamp": self.timestamp,
+            "year": self.puzzle.year,
+            "day": self.puzzle.day,
+            "part": self.puzzle.part,
+            "answer": self.answer,
+            "time_ns": self.time.as_nanos() as u64,
+            "memory": self.memory,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Record {
+            revision: value["revision"].as_str()?.to_string(),
+            machine: value["machine"].as_str()?.to_string(),
+            // records from before the build was saved
+            profile: value["profile"].as_str().unwrap_or("unknown").to_string(),
+            features: value["features"]
+                .as_array()
+                .map(|features| {
+                    features
+                        .iter()
+                        .filter_map(|feature| Some(feature.as_str()?.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            timestamp: value["timestamp"].as_u64()?,
+            puzzle: Puzzle::new(
+                value["year"].as_i64()? as i32,
+                value["day"].as_u64()? as u32,
+                value["part"].as_u64()? as u32,
+            ),
+            answer: value["answer"].as_str()?.to_string(),
+            time: Duration::from_nanos(value["time_ns"].as_u64()?),
+            memory: value["memory"].as_u64()? as usize,
+        })
+    }
+}
+
+// the current commit, with "-dirty" if there are uncommitted changes
+pub fn revision() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", hash)
+        }
+        Some(hash) => hash,
+        None => "unknown".to_string(),
+    }
+}
+
+pub fn profile() -> &'static str {
+    if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    }
+}
+
+// the features that change how fast the solvers are
+pub fn features() -> Vec<String> {
+    let mut features = Vec::new();
+    if cfg!(feature = "checked-arith") {
+        features.push("checked-arith".to_string());
+    }
+    features
+}
+
+pub fn machine() -> String {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .map(|name| name.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+pub fn append(path: &str, records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", record.to_json())?;
+    }
+    Ok(())
+}
+
+// lines that can't be read are skipped, so a half written line doesn't lose the rest
+pub fn load(path: &str) -> io::Result<Vec<Record>> {
+    let contents = match fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        contents => contents?,
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter_map(|value| Record::from_json(&value))
+        .collect())
+}
+
+// how one puzzle built one way did over the revisions, oldest first
+#[derive(Debug, PartialEq)]
+pub struct Trend {
+    pub puzzle: Puzzle,
+    pub profile: String,
+    pub features: Vec<String>,
+    // (revision, fastest time, largest memory) for each revision it was run on
+    pub revisions: Vec<(String, Duration, usize)>,
+    // the latest revision was slower than the one before by more than the threshold
+    pub regressed: bool,
+    // the latest revision gave a different answer than the one before
+    pub answer_changed: bool,
+}
+
+// groups one machine's records by puzzle, build and revision. the fastest run of a revision is used,
+// since the slower ones are mostly noise from whatever else the machine was doing.
+// `threshold` is the allowed slowdown as a fraction, so 0.1 allows 10%
+pub fn trends(records: &[Record], machine: &str, threshold: f64) -> Vec<Trend> {
+    type Key = (Puzzle, String, Vec<String>);
+    let mut by_puzzle: BTreeMap<Key, Vec<(String, Duration, usize, String)>> = BTreeMap::new();
+    for record in records.iter().filter(|r| r.machine == machine) {
+        let key = (
+            record.puzzle,
+            record.profile.clone(),
+            record.features.clone(),
+        );
+        let revisions = by_puzzle.entry(key).or_default();
+        match revisions.last_mut() {
+            Some(last) if last.0 == record.revision => {
+                last.1 = last.1.min(record.time);
+                last.2 = last.2.max(record.memory);
+                last.3 = record.answer.clone();
+            }
+            _ => revisions.push((
+                record.revision.clone(),
+                record.time,
+                record.memory,
+                record.answer.clone(),
+            )),
+        }
+    }
+
+    by_puzzle
+        .into_iter()
+        .map(|((puzzle, profile, features), revisions)| {
+            let (regressed, answer_changed) = match &revisions[..] {
+                [.., previous, latest] => (
+                    latest.1.as_secs_f64() > previous.1.as_secs_f64() * (1.0 + threshold),
+                    latest.3 != previous.3,
+                ),
+                _ => (false, false),
+            };
+            Trend {
+                puzzle,
+                profile,
+                features,
+                revisions: revisions
+                    .into_iter()
+                    .map(|(revision, time, memory, _)| (revision, time, memory))
+                    .collect(),
+                regressed,
+                answer_changed,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(revision: &str, machine: &str, millis: u64, answer: &str) -> Record {
+        Record {
+            revision: revision.to_string(),
+            machine: machine.to_string(),
+            profile: "release".to_string(),
+            features: Vec::new(),
+            timestamp: 0,
+            puzzle: Puzzle::new(2022, 1, 1),
+            answer: answer.to_string(),
+            time: Duration::from_millis(millis),
+            memory: 1024,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut record = record("abc1234", "laptop", 12, "24000");
+        record.features = vec!["checked-arith".to_string()];
+        assert_eq!(Record::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let records = vec![
+            record("a", "laptop", 100, "1"),
+            record("b", "laptop", 150, "1"),
+            record("b", "laptop", 105, "1"),
+            record("b", "desktop", 500, "1"),
+        ];
+        let trend = &trends(&records, "laptop", 0.1)[0];
+        assert_eq!(trend.revisions.len(), 2);
+        assert_eq!(trend.revisions[1].1, Duration::from_millis(105));
+        assert!(!trend.regressed);
+        assert!(trends(&records, "laptop", 0.01)[0].regressed);
+
+        let records = vec![
+            record("a", "laptop", 100, "1"),
+            record("b", "laptop", 100, "2"),
+        ];
+        assert!(trends(&records, "laptop", 0.1)[0].answer_changed);
+
+        // a debug build or checked arithmetic is slower, but that's not a regression
+        let mut debug = record("b", "laptop", 1000, "1");
+        debug.profile = "debug".to_string();
+        let mut checked = record("b", "laptop", 200, "1");
+        checked.features = vec!["checked-arith".to_string()];
+        let records = vec![record("a", "laptop", 100, "1"), debug, checked];
+        let trends = trends(&records, "laptop", 0.1);
+        assert_eq!(trends.len(), 3);
+        assert!(trends.iter().all(|trend| !trend.regressed));
+        assert!(trends.iter().all(|trend| trend.revisions.len() == 1));
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use std::io;
+
+use crate::registry::Puzzle;
+
+// Inputs saved on Windows or by an editor that adds a trailing newline trip up the
+// parsers, so every input goes through `normalize` before a solver sees it:
+// - the byte order mark is dropped
+// - CRLF and lone CR line endings become LF
+// - whitespace at the end of each line is removed, leading whitespace is kept
+//   because some inputs (like the crate drawing in day 5) depend on it
+// - blank lines at the end are removed, and so is the final newline
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let input = input.replace("\r\n", "\n").replace('\r', "\n");
+    let lines: Vec<&str> = input.lines().map(|line| line.trim_end()).collect();
+    lines.join("\n").trim_end_matches('\n').to_string()
+}
+
+// the puzzle's input file, ready to hand to a solver
+pub fn read(puzzle: Puzzle) -> io::Result<String> {
+    read_path(&puzzle.input_path())
+}
+
+pub fn read_path(path: &str) -> io::Result<String> {
+    fs::read_to_string(path).map(|input| normalize(&input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n"), "a\nb");
+        assert_eq!(normalize("a\rb\r"), "a\nb");
+        assert_eq!(normalize("a\n\nb\n\n\n"), "a\n\nb");
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(normalize("\u{feff}a \t\n    [D]  \n"), "a\n    [D]");
+        assert_eq!(normalize(""), "");
+        assert_eq!(normalize("\n \n"), "");
+    }
+}
@@ -0,0 +1,12 @@
+// The parts of the runner that other crates can build on,
+// such as the C library in `ffi`.
+
+pub mod cache;
+pub mod history;
+pub mod input;
+pub mod memory;
+pub mod registry;
+pub mod replay;
+pub mod report;
+#[cfg(feature = "serve")]
+pub mod server;
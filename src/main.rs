@@ -1,68 +1,231 @@
+use std::time::Duration;
+
+#[cfg(feature = "interactive")]
 use chrono::{Datelike, NaiveDate};
+#[cfg(feature = "interactive")]
 use inquire::DateSelect;
 
+#[cfg(feature = "interactive")]
+mod player;
+mod runner;
+
+use advent_of_code_2022::memory::PeakAlloc;
+use advent_of_code_2022::registry::{Puzzle, Registry};
+#[cfg(feature = "interactive")]
+use advent_of_code_2022::replay;
+#[cfg(feature = "serve")]
+use advent_of_code_2022::server::Server;
+
+const USAGE: &str = "Usage:
+    advent_of_code_2022                        pick a puzzle interactively (with the interactive feature)
+    advent_of_code_2022 run <year> <day> <part> [example] [<name>=<value> ...]
+    advent_of_code_2022 params <year> <day>
+    advent_of_code_2022 play <year> <day>      (with the interactive feature)
+    advent_of_code_2022 trace <year> <day> <part> <trace file>
+    advent_of_code_2022 replay <trace file>    (with the interactive feature)
+    advent_of_code_2022 bench [year]
+    advent_of_code_2022 verify [year]
+    advent_of_code_2022 history [regression threshold in percent]
+    advent_of_code_2022 compare [year] [<day> <part>]
+    advent_of_code_2022 report --html <output file>
+    advent_of_code_2022 calories <k> [inventory file, stdin if left out]
+    advent_of_code_2022 balance [inventory file, the day 1 input if left out]
+    advent_of_code_2022 cipher [strategy guide file, the day 2 input if left out] [<name>=<value> ...]
+    advent_of_code_2022 tournament [rounds per match] [seed]
+    advent_of_code_2022 relations [section assignments file, the day 4 input if left out]
+    advent_of_code_2022 serve [address] [time limit in seconds] (with the serve feature)
+
+Options, given before the command:
+    --plugins <directory>    use the days built in plugins/ instead of the built in ones
+    --timeout <seconds>      stop the slow parts that show a progress bar after that long
+    --no-cache               solve again even if `run` has a cached answer";
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+fn parse<T: std::str::FromStr>(arg: &str, what: &str) -> T {
+    arg.parse()
+        .unwrap_or_else(|_| panic!("{} must be a number, got {}\n{}", what, arg, USAGE))
+}
+
+// a mistake in the arguments, reported like an unknown command
+#[cfg(feature = "interactive")]
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    std::process::exit(2);
+}
+
 fn main() {
-    let problems = vec![
-        (day01::part1 as fn(), day01::part2 as fn()),
-        (day02::part1 as fn(), day02::part2 as fn()),
-        (day03::part1 as fn(), day03::part2 as fn()),
-        (day04::part1 as fn(), day04::part2 as fn()),
-        (day05::part1 as fn(), day05::part2 as fn()),
-        (day06::part1 as fn(), day06::part2 as fn()),
-        (day07::part1 as fn(), day07::part2 as fn()),
-        (day08::part1 as fn(), day08::part2 as fn()),
-        (day09::part1 as fn(), day09::part2 as fn()),
-        (day10::part1 as fn(), day10::part2 as fn()),
-        (day11::part1 as fn(), day11::part2 as fn()),
-        (day12::part1 as fn(), day12::part2 as fn()),
-        (day13::part1 as fn(), day13::part2 as fn()),
-        (day14::part1 as fn(), day14::part2 as fn()),
-        (day15::part1 as fn(), day15::part2 as fn()),
-        (day16::part1 as fn(), day16::part2 as fn()),
-        (day17::part1 as fn(), day17::part2 as fn()),
-        (day18::part1 as fn(), day18::part2 as fn()),
-        (day19::part1 as fn(), day19::part2 as fn()),
-        (day20::part1 as fn(), day20::part2 as fn()),
-        (day21::part1 as fn(), day21::part2 as fn()),
-    ];
-
-    let dec_01 = NaiveDate::from_ymd_opt(2022, 12, 1).unwrap();
-    let today_time = chrono::offset::Local::now();
-    let today_date =
-        NaiveDate::from_ymd_opt(today_time.year(), today_time.month(), today_time.day())
-            .unwrap_or(dec_01);
+    let mut registry = Registry::with_all_years();
 
-    let date = DateSelect::new("Select a date")
-        .with_default(NaiveDate::from_ymd_opt(2022, 12, 1).unwrap())
-        .with_min_date(NaiveDate::from_ymd_opt(2022, 12, 1).unwrap())
-        .with_max_date(NaiveDate::from_ymd_opt(2022, 12, 25).unwrap())
-        .with_default(today_date)
-        .prompt();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    let mut options = runner::Options::default();
+    loop {
+        match args[..] {
+            ["--plugins", dir, ..] => {
+                runner::load_plugins(&mut registry, dir);
+                args.drain(..2);
+            }
+            ["--timeout", seconds, ..] => {
+                options.time_limit = Some(Duration::from_secs_f64(parse(seconds, "Timeout")));
+                args.drain(..2);
+            }
+            ["--no-cache", ..] => {
+                options.use_cache = false;
+                args.drain(..1);
+            }
+            _ => break,
+        }
+    }
+    runner::handle_ctrl_c();
 
-    //parse into number 1-25
-    let day: usize = date.unwrap().day() as usize;
+    match args[..] {
+        #[cfg(feature = "interactive")]
+        [] => pick(&registry, options),
+        ["run", year, day, part, ref rest @ ..] => {
+            let puzzle = Puzzle::new(parse(year, "Year"), parse(day, "Day"), parse(part, "Part"));
+            let example = rest.first() == Some(&"example");
+            let overrides = rest[example as usize..]
+                .iter()
+                .map(|arg| {
+                    params::parse_override(arg).unwrap_or_else(|e| panic!("{}\n{}", e, USAGE))
+                })
+                .collect();
+            runner::run_with(&registry, puzzle, example, overrides, options);
+        }
+        ["params", year, day] => {
+            runner::list_parameters(&registry, parse(year, "Year"), parse(day, "Day"));
+        }
+        #[cfg(feature = "interactive")]
+        ["play", year, day] => {
+            let (year, day) = (parse(year, "Year"), parse(day, "Day"));
+            let Some(animation) = registry.animation(year, day) else {
+                usage_error(&format!("Day {} of {} has no animation", day, year));
+            };
+            let input = runner::read_input(Puzzle::new(year, day, 1));
+            player::play(&format!("Day {} ({})", day, year), &input, animation)
+                .expect("Error in player");
+        }
+        ["trace", year, day, part, path] => {
+            let puzzle = Puzzle::new(parse(year, "Year"), parse(day, "Day"), parse(part, "Part"));
+            runner::trace(&registry, puzzle, path);
+        }
+        #[cfg(feature = "interactive")]
+        ["replay", path] => {
+            let trace = std::fs::read_to_string(path)
+                .unwrap_or_else(|e| panic!("Error reading {}: {}", path, e));
+            player::play(&format!("Replay of {}", path), &trace, replay::animate)
+                .expect("Error in player");
+        }
+        ["bench"] => runner::bench(&registry, None),
+        ["bench", year] => runner::bench(&registry, Some(parse(year, "Year"))),
+        ["verify"] | ["verify", _] => {
+            let year = args.get(1).map(|year| parse(year, "Year"));
+            if !runner::verify(&registry, year) {
+                std::process::exit(1);
+            }
+        }
+        ["history"] | ["history", _] => {
+            let threshold = args.get(1).map_or(10.0, |t| parse(t, "Threshold"));
+            if !runner::history(threshold) {
+                std::process::exit(1);
+            }
+        }
+        ["compare", year, day, part] => {
+            let puzzle = Puzzle::new(parse(year, "Year"), parse(day, "Day"), parse(part, "Part"));
+            if !runner::compare(&registry, puzzle) {
+                std::process::exit(1);
+            }
+        }
+        ["compare"] | ["compare", _] => {
+            let year = args.get(1).map(|year| parse(year, "Year"));
+            let mut agree = true;
+            for puzzle in registry.puzzles_with_variants(year) {
+                agree &= runner::compare(&registry, puzzle);
+            }
+            if !agree {
+                std::process::exit(1);
+            }
+        }
+        ["report", "--html", path] => runner::report(&registry, path, options),
+        #[cfg(feature = "day01")]
+        ["balance"] | ["balance", _] => runner::balance(args.get(1).copied()),
+        #[cfg(feature = "day01")]
+        ["calories", k] | ["calories", k, _] => {
+            runner::calories(parse(k, "k"), args.get(2).copied())
+        }
+        #[cfg(feature = "day02")]
+        // anything without an = is the file, there can be one
+        ["cipher", ref rest @ ..] if rest.iter().filter(|arg| !arg.contains('=')).count() <= 1 => {
+            let (overrides, path): (Vec<&str>, Vec<&str>) =
+                rest.iter().partition(|arg| arg.contains('='));
+            let overrides: Vec<(String, String)> = overrides
+                .iter()
+                .map(|arg| {
+                    params::parse_override(arg).unwrap_or_else(|e| panic!("{}\n{}", e, USAGE))
+                })
+                .collect();
+            runner::cipher(path.first().copied(), &overrides)
+        }
+        #[cfg(feature = "day02")]
+        ["tournament"] | ["tournament", _] | ["tournament", _, _] => {
+            let rounds = args.get(1).map_or(1000, |s| parse(s, "Rounds"));
+            let seed = args.get(2).map_or(2022, |s| parse(s, "Seed"));
+            runner::tournament(rounds, seed)
+        }
+        #[cfg(feature = "day04")]
+        ["relations"] | ["relations", _] => runner::relations(args.get(1).copied()),
+        #[cfg(feature = "serve")]
+        ["serve"] | ["serve", _] | ["serve", _, _] => {
+            let addr = args.get(1).copied().unwrap_or("127.0.0.1:8080");
+            let seconds = args.get(2).map_or(10, |s| parse(s, "Time limit"));
+            let server = Server::bind(addr, registry, Duration::from_secs(seconds))
+                .unwrap_or_else(|e| panic!("Error listening on {}: {}", addr, e));
+            println!("Listening on http://{}", server.addr());
+            server.run();
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
 
-    //get number 1 or 2
-    let options = vec!["Part 1", "Part 2"];
-    let part = inquire::Select::new("Select a part", options.clone())
+#[cfg(feature = "interactive")]
+fn pick(registry: &Registry, options: runner::Options) {
+    let years = registry.years();
+    let first = NaiveDate::from_ymd_opt(years[0], 12, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(*years.last().unwrap(), 12, 25).unwrap();
+    let today = chrono::offset::Local::now().date_naive();
+    let default = if today >= first && today <= last {
+        today
+    } else {
+        NaiveDate::from_ymd_opt(*years.last().unwrap(), 12, 1).unwrap()
+    };
+
+    let date = DateSelect::new("Select a date")
+        .with_min_date(first)
+        .with_max_date(last)
+        .with_default(default)
         .prompt()
         .unwrap();
 
-    //run the function
-    if problems.len() < day {
-        unimplemented!("Day {} not implemented yet", day);
+    if date.month() != 12 || date.day() > 25 {
+        println!("There is no puzzle on {}", date);
+        return;
     }
 
-    let (part1, part2) = problems[day - 1];
-    if part == options[0] {
-        println!("Running Part 1 of Day {}", day);
-        println!("========================");
-        part1();
-    } else if part == options[1] {
-        println!("Running Part 2 of Day {}", day);
-        println!("========================");
-        part2();
-    }
-}
+    //get number 1 or 2
+    let parts = vec!["Part 1", "Part 2"];
+    let part = inquire::Select::new("Select a part", parts.clone())
+        .prompt()
+        .unwrap();
+    let part = if part == parts[0] { 1 } else { 2 };
 
-pub fn part2() {}
+    runner::run(
+        registry,
+        Puzzle::new(date.year(), date.day(), part),
+        options,
+    );
+}
@@ -0,0 +1,234 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+use advent_of_code_2022::registry::Animation;
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+enum Key {
+    Toggle,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+    Other,
+}
+
+fn read_key(key: KeyEvent) -> Key {
+    match key.code {
+        KeyCode::Char(' ') => Key::Toggle,
+        KeyCode::Char('n') | KeyCode::Right => Key::Step,
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => Key::Faster,
+        KeyCode::Char('-') | KeyCode::Down => Key::Slower,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Key::Quit,
+        KeyCode::Char('q') | KeyCode::Esc => Key::Quit,
+        _ => Key::Other,
+    }
+}
+
+// the first of `lines` lines to show on a screen `height` lines high. keeps the focus line
+// in the middle of the screen, unless that would scroll past either end of the frame
+fn first_visible(lines: usize, height: usize, focus: usize) -> usize {
+    focus
+        .saturating_sub(height / 2)
+        .min(lines.saturating_sub(height))
+}
+
+// raw mode and the alternate screen for as long as it lives. the terminal is put back
+// when it's dropped, also when setting it up fails halfway or drawing fails
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        let screen = Screen;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        // nothing else to try if the terminal can't be put back
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct Player {
+    title: String,
+    delay: Duration,
+    paused: bool,
+    frame: usize,
+}
+
+impl Player {
+    fn new(title: &str) -> Self {
+        Player {
+            title: title.to_string(),
+            delay: Duration::from_millis(100),
+            paused: false,
+            frame: 0,
+        }
+    }
+
+    fn faster(&mut self) {
+        self.delay = (self.delay / 2).max(MIN_DELAY);
+    }
+
+    fn slower(&mut self) {
+        self.delay = (self.delay * 2).min(MAX_DELAY);
+    }
+
+    fn draw(&self, text: &str, focus: usize, status: &str) -> io::Result<()> {
+        let (cols, rows) = terminal::size()?;
+        let height = (rows as usize).saturating_sub(1).max(1);
+        let lines: Vec<&str> = text.lines().collect();
+        let start = first_visible(lines.len(), height, focus);
+
+        let mut stdout = io::stdout();
+        queue!(stdout, terminal::Clear(ClearType::All))?;
+        for (row, line) in lines.iter().skip(start).take(height).enumerate() {
+            let line: String = line.chars().take(cols as usize).collect();
+            queue!(stdout, cursor::MoveTo(0, row as u16), Print(line))?;
+        }
+        let status: String = status.chars().take(cols as usize).collect();
+        queue!(stdout, cursor::MoveTo(0, height as u16), Print(status))?;
+        stdout.flush()
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "{} | frame {} | {}ms | {} | space: play/pause  n: step  +/-: speed  q: quit",
+            self.title,
+            self.frame,
+            self.delay.as_millis(),
+            if self.paused { "paused" } else { "playing" }
+        )
+    }
+
+    // draws a frame and waits until it is time for the next one
+    // returns false if the user wants to quit
+    fn show(&mut self, text: &str, focus: usize) -> io::Result<bool> {
+        self.frame += 1;
+        loop {
+            self.draw(text, focus, &self.status())?;
+            if self.paused {
+                if let Event::Key(key) = event::read()? {
+                    match read_key(key) {
+                        Key::Toggle => self.paused = false,
+                        Key::Step => return Ok(true),
+                        Key::Faster => self.faster(),
+                        Key::Slower => self.slower(),
+                        Key::Quit => return Ok(false),
+                        Key::Other => {}
+                    }
+                }
+                if self.paused {
+                    continue;
+                }
+                return Ok(true);
+            }
+
+            let deadline = Instant::now() + self.delay;
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(true);
+                }
+                if !event::poll(remaining)? {
+                    continue;
+                }
+                if let Event::Key(key) = event::read()? {
+                    match read_key(key) {
+                        Key::Toggle => {
+                            self.paused = true;
+                            break;
+                        }
+                        Key::Faster => self.faster(),
+                        Key::Slower => self.slower(),
+                        Key::Quit => return Ok(false),
+                        Key::Step | Key::Other => {}
+                    }
+                }
+            }
+        }
+    }
+
+    // keeps the last frame on screen until the user quits
+    fn finish(&mut self, text: &str, focus: usize) -> io::Result<()> {
+        self.draw(
+            text,
+            focus,
+            &format!(
+                "{} | frame {} | done, press q to quit",
+                self.title, self.frame
+            ),
+        )?;
+        loop {
+            if let Event::Key(key) = event::read()? {
+                if let Key::Quit = read_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+pub fn play(title: &str, input: &str, animation: Animation) -> io::Result<()> {
+    let _screen = Screen::enter()?;
+
+    let mut player = Player::new(title);
+    let mut result = Ok(());
+    let mut quit = false;
+    let mut last = (String::new(), 0);
+    animation(input, &mut |text, focus| {
+        last = (text.to_string(), focus);
+        match player.show(text, focus) {
+            Ok(keep_going) => {
+                quit = !keep_going;
+                keep_going
+            }
+            Err(e) => {
+                result = Err(e);
+                false
+            }
+        }
+    });
+    if result.is_ok() && !quit {
+        result = player.finish(&last.0, last.1);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_stays_in_the_middle() {
+        // 100 lines on a 10 line screen
+        assert_eq!(first_visible(100, 10, 50), 45);
+        assert_eq!(first_visible(100, 10, 5), 0);
+        assert_eq!(first_visible(100, 10, 6), 1);
+    }
+
+    #[test]
+    fn never_scrolls_past_the_ends() {
+        assert_eq!(first_visible(100, 10, 0), 0);
+        assert_eq!(first_visible(100, 10, 97), 90);
+        assert_eq!(first_visible(100, 10, 500), 90);
+        // a frame shorter than the screen is shown from the top
+        assert_eq!(first_visible(3, 10, 2), 0);
+        assert_eq!(first_visible(0, 10, 0), 0);
+    }
+}
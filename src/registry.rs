@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use plugin::Plugin;
+use progress::{Progress, Stopped};
+
+// takes the puzzle input and returns the answer
+pub type Solver = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+// how the year crates hand over their solvers
+pub type SolverFn = fn(&str) -> String;
+
+// takes the puzzle input and `name=value` overrides for the day's parameters
+pub type ParamSolver = fn(&str, &[(String, String)]) -> Result<String, String>;
+
+// the names and default values of a day's parameters
+pub type Defaults = fn() -> Vec<(&'static str, String)>;
+
+// (day, parameters, [(part, solver)])
+pub type DayParameters = (u32, Defaults, Vec<(u32, ParamSolver)>);
+
+// takes the puzzle input and writes what happens while solving it to the tracer
+pub type TracedSolver = fn(&str, &mut trace::Tracer) -> String;
+
+// takes the puzzle input and reports how far along it is, returns
+// `Err(Stopped::Cancelled)` if it was stopped through the `Progress`
+pub type ProgressSolver = fn(&str, &Progress) -> Result<String, Stopped>;
+
+// a simulation that draws itself by calling the closure once per step,
+// with the rendered frame and the line that should be kept in view.
+// the simulation stops as soon as the closure returns false
+pub type Animation = fn(&str, &mut dyn FnMut(&str, usize) -> bool);
+
+// one part of one day's puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: i32,
+    pub day: u32,
+    pub part: u32,
+}
+
+impl Puzzle {
+    pub fn new(year: i32, day: u32, part: u32) -> Self {
+        Puzzle { year, day, part }
+    }
+
+    // every year has its own input directory
+    pub fn input_path(&self) -> String {
+        format!("input/{}/day{:02}.in", self.year, self.day)
+    }
+
+    // the example from the puzzle text, if it was saved
+    pub fn example_path(&self) -> String {
+        format!("input/{}/day{:02}.example", self.year, self.day)
+    }
+
+    // the known correct answer, used by `verify`
+    pub fn answer_path(&self) -> String {
+        format!(
+            "input/{}/day{:02}.part{}.answer",
+            self.year, self.day, self.part
+        )
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {:02} part {}", self.year, self.day, self.part)
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solvers: BTreeMap<Puzzle, Solver>,
+    variants: BTreeMap<Puzzle, Vec<(&'static str, Solver)>>,
+    animations: BTreeMap<(i32, u32), Animation>,
+    parameters: BTreeMap<(i32, u32), Defaults>,
+    param_solvers: BTreeMap<Puzzle, ParamSolver>,
+    traced: BTreeMap<Puzzle, TracedSolver>,
+    progress: BTreeMap<Puzzle, ProgressSolver>,
+    sources: BTreeMap<(i32, u32), u64>,
+}
+
+// everything a year crate hands over, see year2022 for what each list holds
+#[derive(Default)]
+pub struct Year {
+    pub year: i32,
+    pub solutions: Vec<(u32, SolverFn, SolverFn)>,
+    pub variants: Vec<(u32, u32, &'static str, SolverFn)>,
+    pub animations: Vec<(u32, Animation)>,
+    pub parameters: Vec<DayParameters>,
+    pub traced: Vec<(u32, u32, TracedSolver)>,
+    pub progress: Vec<(u32, u32, ProgressSolver)>,
+    // (day, hash of the day's sources)
+    pub sources: Vec<(u32, u64)>,
+}
+
+impl Registry {
+    // every year crate the runner is built with
+    pub fn with_all_years() -> Self {
+        let mut registry = Registry::default();
+        registry.add_year(Year {
+            year: year2022::YEAR,
+            solutions: year2022::solutions(),
+            variants: year2022::variants(),
+            animations: year2022::animations(),
+            parameters: year2022::parameters(),
+            traced: year2022::traced(),
+            progress: year2022::progress(),
+            sources: year2022::sources(),
+        });
+        registry
+    }
+
+    pub fn add_year(&mut self, solvers: Year) {
+        let year = solvers.year;
+        for (day, part1, part2) in solvers.solutions {
+            self.solvers
+                .insert(Puzzle::new(year, day, 1), Arc::new(part1));
+            self.solvers
+                .insert(Puzzle::new(year, day, 2), Arc::new(part2));
+        }
+        for (day, part, name, solver) in solvers.variants {
+            self.variants
+                .entry(Puzzle::new(year, day, part))
+                .or_default()
+                .push((name, Arc::new(solver) as Solver));
+        }
+        for (day, animation) in solvers.animations {
+            self.animations.insert((year, day), animation);
+        }
+        for (day, defaults, param_solvers) in solvers.parameters {
+            self.parameters.insert((year, day), defaults);
+            for (part, solver) in param_solvers {
+                self.param_solvers
+                    .insert(Puzzle::new(year, day, part), solver);
+            }
+        }
+        for (day, part, solver) in solvers.traced {
+            self.traced.insert(Puzzle::new(year, day, part), solver);
+        }
+        for (day, part, solver) in solvers.progress {
+            self.progress.insert(Puzzle::new(year, day, part), solver);
+        }
+        for (day, hash) in solvers.sources {
+            self.sources.insert((year, day), hash);
+        }
+    }
+
+    // replaces the day's built in solvers with the plugin's,
+    // the built in ones stay available as the "built in" variant for `compare`.
+    // the plugin's sources aren't known, so its answers aren't cached
+    pub fn add_plugin(&mut self, plugin: Plugin) {
+        let plugin = Arc::new(plugin);
+        let (year, day) = (plugin.year(), plugin.day());
+        self.sources.remove(&(year, day));
+        for part in 1..=2 {
+            let puzzle = Puzzle::new(year, day, part);
+            let plugin = Arc::clone(&plugin);
+            let solver: Solver = Arc::new(move |input: &str| {
+                plugin
+                    .solve(part, input)
+                    .unwrap_or_else(|e| panic!("{}", e))
+            });
+            if let Some(built_in) = self.solvers.insert(puzzle, solver) {
+                self.variants
+                    .entry(puzzle)
+                    .or_default()
+                    .insert(0, ("built in", built_in));
+            }
+            // these would run the built in code instead of the plugin
+            self.param_solvers.remove(&puzzle);
+            self.progress.remove(&puzzle);
+        }
+    }
+
+    pub fn solver(&self, puzzle: Puzzle) -> Option<Solver> {
+        self.solvers.get(&puzzle).cloned()
+    }
+
+    // the main solver, called "default", followed by any other variants
+    pub fn variants(&self, puzzle: Puzzle) -> Vec<(&'static str, Solver)> {
+        let mut variants: Vec<_> = self
+            .solver(puzzle)
+            .map(|s| ("default", s))
+            .into_iter()
+            .collect();
+        if let Some(others) = self.variants.get(&puzzle) {
+            variants.extend(others.iter().cloned());
+        }
+        variants
+    }
+
+    // puzzles that have at least one variant besides the main solver
+    pub fn puzzles_with_variants(&self, year: Option<i32>) -> Vec<Puzzle> {
+        self.variants
+            .keys()
+            .filter(|p| year.is_none() || year == Some(p.year))
+            .copied()
+            .collect()
+    }
+
+    // only for the parts that use the day's parameters
+    pub fn param_solver(&self, puzzle: Puzzle) -> Option<ParamSolver> {
+        self.param_solvers.get(&puzzle).copied()
+    }
+
+    // (name, default value) of each of the day's parameters
+    pub fn parameters(&self, year: i32, day: u32) -> Vec<(&'static str, String)> {
+        self.parameters
+            .get(&(year, day))
+            .map_or_else(Vec::new, |defaults| defaults())
+    }
+
+    pub fn traced(&self, puzzle: Puzzle) -> Option<TracedSolver> {
+        self.traced.get(&puzzle).copied()
+    }
+
+    // only for the slow parts that can show progress and be stopped
+    pub fn progress_solver(&self, puzzle: Puzzle) -> Option<ProgressSolver> {
+        self.progress.get(&puzzle).copied()
+    }
+
+    // changes whenever the day's crate does, `None` for days that can't be cached
+    pub fn source_hash(&self, year: i32, day: u32) -> Option<u64> {
+        self.sources.get(&(year, day)).copied()
+    }
+
+    pub fn animation(&self, year: i32, day: u32) -> Option<Animation> {
+        self.animations.get(&(year, day)).copied()
+    }
+
+    // sorted, oldest first
+    pub fn years(&self) -> Vec<i32> {
+        let mut years: Vec<i32> = self.solvers.keys().map(|p| p.year).collect();
+        years.dedup();
+        years
+    }
+
+    // every registered puzzle in order, optionally only for one year
+    pub fn puzzles(&self, year: Option<i32>) -> Vec<Puzzle> {
+        self.solvers
+            .keys()
+            .filter(|p| year.is_none() || year == Some(p.year))
+            .copied()
+            .collect()
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::Value;
+
+// how many of the latest text events are shown above the grid
+const LOG_LINES: usize = 5;
+
+// Draws a trace written by a `Tracer` one event per frame, without running the solver again.
+// Events with a glyph stay on a grid, the others scroll past in a small log above it.
+// Has the same shape as `registry::Animation`, so the player can show it.
+pub fn animate(trace: &str, frame: &mut dyn FnMut(&str, usize) -> bool) {
+    let events = parse(trace);
+    let y_up = events.iter().any(|event| event["event"] == "y_up");
+    let marks: Vec<(i64, i64)> = events
+        .iter()
+        .filter_map(mark)
+        .map(|(x, y, _)| (x, y))
+        .collect();
+    let (min_x, max_x) = bounds(marks.iter().map(|(x, _)| *x));
+    let (min_y, max_y) = bounds(marks.iter().map(|(_, y)| *y));
+
+    let mut grid: HashMap<(i64, i64), char> = HashMap::new();
+    let mut log: VecDeque<String> = VecDeque::new();
+    for (i, event) in events.iter().enumerate() {
+        if event["event"] == "y_up" {
+            continue;
+        }
+        let mut focus = 0;
+        match mark(event) {
+            Some((x, y, glyph)) => {
+                grid.insert((x, y), glyph);
+                focus = if y_up { max_y - y } else { y - min_y } as usize;
+            }
+            None => {
+                log.push_back(describe(event));
+                if log.len() > LOG_LINES {
+                    log.pop_front();
+                }
+            }
+        }
+
+        let mut text = format!("step {}/{}: {}\n", i + 1, events.len(), describe(event));
+        for line in &log {
+            text.push_str(line);
+            text.push('\n');
+        }
+        for _ in log.len()..LOG_LINES {
+            text.push('\n');
+        }
+        text.push('\n');
+        if !marks.is_empty() {
+            let rows: Vec<i64> = if y_up {
+                (min_y..=max_y).rev().collect()
+            } else {
+                (min_y..=max_y).collect()
+            };
+            for y in rows {
+                for x in min_x..=max_x {
+                    text.push(*grid.get(&(x, y)).unwrap_or(&'.'));
+                }
+                text.push('\n');
+            }
+        }
+
+        if !frame(&text, LOG_LINES + 2 + focus) {
+            return;
+        }
+    }
+}
+
+fn parse(trace: &str) -> Vec<Value> {
+    trace
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("Invalid trace line {}: {}", line, e))
+        })
+        .collect()
+}
+
+// the grid as it looks after the last step, and whether y grows upwards
+pub fn final_grid(trace: &str) -> (HashMap<(i64, i64), char>, bool) {
+    let events = parse(trace);
+    let y_up = events.iter().any(|event| event["event"] == "y_up");
+    let grid = events
+        .iter()
+        .filter_map(mark)
+        .map(|(x, y, glyph)| ((x, y), glyph))
+        .collect();
+    (grid, y_up)
+}
+
+// (x, y, glyph) of an event that is drawn on the grid
+fn mark(event: &Value) -> Option<(i64, i64, char)> {
+    let glyph = event["glyph"].as_str()?.chars().next()?;
+    Some((event["x"].as_i64()?, event["y"].as_i64()?, glyph))
+}
+
+fn bounds(values: impl Iterator<Item = i64>) -> (i64, i64) {
+    values.fold((i64::MAX, i64::MIN), |(min, max), v| {
+        (min.min(v), max.max(v))
+    })
+}
+
+// "register cycle=20 x=21"
+fn describe(event: &Value) -> String {
+    let mut text = event["event"].as_str().unwrap_or("?").to_string();
+    if let Some(fields) = event.as_object() {
+        for (key, value) in fields {
+            if key != "event" && key != "step" && key != "glyph" {
+                text.push_str(&format!(" {}={}", key, value));
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_marks_and_logs_events() {
+        let trace = r##"{"step":1,"event":"y_up"}
+{"step":2,"event":"rock_spawned","rock":0,"x":2,"y":4}
+{"step":3,"event":"rock_at_rest","x":0,"y":1,"glyph":"#"}
+{"step":4,"event":"rock_at_rest","x":1,"y":2,"glyph":"@"}"##;
+        let mut frames = Vec::new();
+        animate(trace, &mut |text, focus| {
+            frames.push((text.to_string(), focus));
+            true
+        });
+
+        assert_eq!(frames.len(), 3);
+        assert!(frames[0]
+            .0
+            .starts_with("step 2/4: rock_spawned rock=0 x=2 y=4\n"));
+        // y grows upwards, so the last mark is on the top row
+        let grid: Vec<&str> = frames[2].0.lines().skip(LOG_LINES + 2).collect();
+        assert_eq!(grid, vec![".@", "#."]);
+        assert_eq!(frames[2].1, LOG_LINES + 2);
+    }
+}
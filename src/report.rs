@@ -0,0 +1,240 @@
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::time::Duration;
+
+use trace::Tracer;
+
+use crate::history::Record;
+use crate::memory::format_bytes;
+use crate::registry::{Puzzle, TracedSolver};
+use crate::replay;
+
+// The page written by `report --html`: the answers, a chart of how long each part took
+// and a picture of the days that can be traced. Everything is inline, so the file can be
+// sent around on its own.
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; }
+table { border-collapse: collapse; }
+td, th { padding: 0.2em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.number { text-align: right; }
+pre { margin: 0; }
+.picture { overflow: auto; max-height: 40em; border: 1px solid #ddd; }";
+
+// how wide the timing chart and the pictures are drawn, in pixels
+const WIDTH: usize = 800;
+
+// a writer that can still be read after handing it to a `Tracer`
+#[derive(Clone, Default)]
+struct Captured(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// solves the puzzle with a tracer, returns the answer and a drawing of where the trace
+// left its marks, `None` if the trace has no marks to draw
+pub fn picture(solver: TracedSolver, input: &str) -> (String, Option<String>) {
+    let captured = Captured::default();
+    let mut tracer = Tracer::new(captured.clone());
+    let answer = solver(input, &mut tracer);
+    let picture = tracer
+        .finish()
+        .ok()
+        .and_then(|_| String::from_utf8(captured.0.take()).ok())
+        .and_then(|trace| svg(&trace));
+    (answer, picture)
+}
+
+// one rectangle per run of the same glyph in a row, which keeps the bigger traces small
+fn svg(trace: &str) -> Option<String> {
+    let (grid, y_up) = replay::final_grid(trace);
+    let min_x = grid.keys().map(|(x, _)| *x).min()?;
+    let max_x = grid.keys().map(|(x, _)| *x).max()?;
+    let min_y = grid.keys().map(|(_, y)| *y).min()?;
+    let max_y = grid.keys().map(|(_, y)| *y).max()?;
+    let (width, height) = ((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+    let scale = (WIDTH / width).clamp(1, 12);
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+        width * scale,
+        height * scale,
+        width,
+        height
+    );
+    let _ = write!(
+        svg,
+        r##"<rect width="{}" height="{}" fill="#f8f8f8"/>"##,
+        width, height
+    );
+    for y in min_y..=max_y {
+        let row = if y_up { max_y - y } else { y - min_y };
+        let mut x = min_x;
+        while x <= max_x {
+            let Some(&glyph) = grid.get(&(x, y)) else {
+                x += 1;
+                continue;
+            };
+            let start = x;
+            while grid.get(&(x + 1, y)) == Some(&glyph) {
+                x += 1;
+            }
+            let _ = write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="1" fill="{}"/>"#,
+                start - min_x,
+                row,
+                x - start + 1,
+                color(glyph)
+            );
+            x += 1;
+        }
+    }
+    svg.push_str("</svg>");
+    Some(svg)
+}
+
+// letters are heights, like in day 12, and go from dark to light green
+fn color(glyph: char) -> String {
+    match glyph {
+        '#' => "#444".to_string(),
+        'o' => "#d4a017".to_string(),
+        '*' => "#d62728".to_string(),
+        'a'..='z' => {
+            let height = glyph as u32 - 'a' as u32;
+            format!("hsl(100, 35%, {}%)", 20 + height * 3)
+        }
+        _ => "#1f77b4".to_string(),
+    }
+}
+
+// a bar per part on a log scale, since the slowest parts take a million times
+// longer than the fastest ones
+fn timing_chart(records: &[Record]) -> String {
+    const LABEL: usize = 130;
+    const ROW: usize = 18;
+    let micros = |time: Duration| (time.as_secs_f64() * 1e6).max(1.0);
+    let slowest = records.iter().map(|r| micros(r.time)).fold(10.0, f64::max);
+    let bar_width = (WIDTH - LABEL - 90) as f64;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-size="12">"#,
+        WIDTH,
+        records.len() * ROW
+    );
+    for (i, record) in records.iter().enumerate() {
+        let y = i * ROW;
+        let width = (micros(record.time).ln() / slowest.ln() * bar_width).max(1.0);
+        let puzzle = record.puzzle;
+        let _ = write!(
+            svg,
+            r##"<text x="0" y="{}">{} day {:02} part {}</text><rect x="{}" y="{}" width="{:.1}" height="{}" fill="#1f77b4"/><text x="{:.1}" y="{}">{:?}</text>"##,
+            y + 13,
+            puzzle.year,
+            puzzle.day,
+            puzzle.part,
+            LABEL,
+            y + 3,
+            width,
+            ROW - 5,
+            LABEL as f64 + width + 5.0,
+            y + 13,
+            record.time
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// `records` in puzzle order, `pictures` are SVGs from `picture`
+pub fn html(records: &[Record], pictures: &[(Puzzle, String)]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code</title>\n<style>\n{}\n</style>\n</head>\n<body>\n<h1>Advent of Code</h1>\n",
+        STYLE
+    );
+    if let Some(record) = records.first() {
+        let _ = writeln!(
+            html,
+            "<p>Revision {} on {}</p>",
+            escape(&record.revision),
+            escape(&record.machine)
+        );
+    }
+
+    html.push_str("<h2>Answers</h2>\n<table>\n<tr><th>Puzzle</th><th>Answer</th><th>Time</th><th>Memory</th></tr>\n");
+    for record in records {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td><pre>{}</pre></td><td class=\"number\">{:?}</td><td class=\"number\">{}</td></tr>",
+            record.puzzle,
+            escape(&record.answer),
+            record.time,
+            format_bytes(record.memory)
+        );
+    }
+    html.push_str("</table>\n");
+
+    let _ = writeln!(
+        html,
+        "<h2>Timing</h2>\n<p>Log scale</p>\n{}",
+        timing_chart(records)
+    );
+
+    if !pictures.is_empty() {
+        html.push_str("<h2>Pictures</h2>\n");
+    }
+    for (puzzle, svg) in pictures {
+        let _ = writeln!(
+            html,
+            "<h3>{}</h3>\n<div class=\"picture\">{}</div>",
+            puzzle, svg
+        );
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_runs_of_marks() {
+        let trace = r##"{"step":1,"event":"pixel","x":0,"y":0,"glyph":"#"}
+{"step":2,"event":"pixel","x":1,"y":0,"glyph":"#"}
+{"step":3,"event":"path","x":1,"y":1,"glyph":"*"}"##;
+        let drawn = svg(trace).unwrap();
+        assert!(drawn.contains(r##"<rect x="0" y="0" width="2" height="1" fill="#444"/>"##));
+        assert!(drawn.contains(r##"<rect x="1" y="1" width="1" height="1" fill="#d62728"/>"##));
+        assert_eq!(svg(r#"{"step":1,"event":"register","x":1}"#), None);
+    }
+
+    #[test]
+    fn escapes_answers() {
+        let record = Record::new(
+            "abc1234",
+            Puzzle::new(2022, 10, 2),
+            "<#>",
+            Duration::from_millis(1),
+            10,
+        );
+        let html = html(&[record], &[]);
+        assert!(html.contains("<pre>&lt;#&gt;</pre>"));
+        assert!(!html.contains("<h2>Pictures</h2>"));
+    }
+}
@@ -0,0 +1,723 @@
+use std::fs;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2022::cache::{self, Cache, Entry, Key, CACHE_PATH};
+use advent_of_code_2022::history::{self, Record, HISTORY_PATH};
+use advent_of_code_2022::memory::{self, format_bytes};
+use advent_of_code_2022::registry::{ProgressSolver, Puzzle, Registry, Solver};
+use advent_of_code_2022::{input, report};
+use progress::{Progress, Stopped};
+use trace::Tracer;
+
+// the solver Ctrl-C should stop, if one is running
+static RUNNING: Mutex<Option<Progress>> = Mutex::new(None);
+
+// set by the options given before the command
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    // stops the slow parts that show a progress bar after this long
+    pub time_limit: Option<Duration>,
+    // answers from `CACHE_PATH` are shown instead of solving again
+    pub use_cache: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            time_limit: None,
+            use_cache: true,
+        }
+    }
+}
+
+// for asking for something that doesn't exist, like a puzzle without a solver
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    process::exit(1);
+}
+
+pub fn read_input(puzzle: Puzzle) -> String {
+    input::read(puzzle).unwrap_or_else(|e| panic!("Error reading {}: {}", puzzle.input_path(), e))
+}
+
+// (answer, time, peak heap bytes)
+fn measure<T>(solve: impl FnOnce() -> T) -> (T, Duration, usize) {
+    memory::reset_peak();
+    let time = Instant::now();
+    let answer = solve();
+    (answer, time.elapsed(), memory::peak())
+}
+
+fn solve(solver: Solver, input: &str) -> (String, Duration, usize) {
+    measure(|| solver(input))
+}
+
+// failing to save the history shouldn't lose the results that were just printed
+fn save_history(records: &[Record]) {
+    if let Err(e) = history::append(HISTORY_PATH, records) {
+        eprintln!("Error saving history to {}: {}", HISTORY_PATH, e);
+    }
+}
+
+// Ctrl-C stops a solver that can be stopped and lets the runner report it,
+// anything else is killed like it would be without the handler
+pub fn handle_ctrl_c() {
+    ctrlc::set_handler(|| match RUNNING.lock().unwrap().as_ref() {
+        Some(progress) => progress.cancel(),
+        None => process::exit(130),
+    })
+    .expect("Error setting the Ctrl-C handler");
+}
+
+// a broken cache only means solving again
+fn open_cache() -> Option<Cache> {
+    Cache::open(CACHE_PATH)
+        .map_err(|e| eprintln!("Error reading the cache {}: {}", CACHE_PATH, e))
+        .ok()
+}
+
+// `None` for days whose sources aren't known, like the ones from plugins
+// `variant` is what was run, as the same answer can take a different time another way
+fn cache_key(registry: &Registry, puzzle: Puzzle, variant: &str, input: &str) -> Option<Key> {
+    registry
+        .source_hash(puzzle.year, puzzle.day)
+        .map(|source| Key {
+            puzzle,
+            variant: variant.to_string(),
+            source,
+            input: cache::hash(input),
+        })
+}
+
+fn cached(cache: &Option<Cache>, key: &Option<Key>) -> Option<Entry> {
+    cache.as_ref()?.get(key.as_ref()?).cloned()
+}
+
+fn save_to_cache(cache: &mut Option<Cache>, key: Option<Key>, entry: Entry) {
+    if let (Some(cache), Some(key)) = (cache.as_mut(), key) {
+        if let Err(e) = cache.insert(key, entry) {
+            eprintln!("Error saving to the cache {}: {}", CACHE_PATH, e);
+        }
+    }
+}
+
+// for the interactive picker
+#[cfg(feature = "interactive")]
+pub fn run(registry: &Registry, puzzle: Puzzle, options: Options) {
+    run_with(registry, puzzle, false, Vec::new(), options);
+}
+
+// runs on the real input or the example, with the day's parameters overridden
+// first by the `.params` file next to the input (if there is one) and then by `overrides`.
+// an answer cached for the same sources and input is shown right away,
+// unless the cache is turned off in `options`
+pub fn run_with(
+    registry: &Registry,
+    puzzle: Puzzle,
+    example: bool,
+    overrides: Vec<(String, String)>,
+    options: Options,
+) {
+    let Some(solver) = registry.solver(puzzle) else {
+        exit_with_error(&format!("{} is not implemented yet", puzzle));
+    };
+    let path = if example {
+        puzzle.example_path()
+    } else {
+        puzzle.input_path()
+    };
+    let input = input::read_path(&path).unwrap_or_else(|e| panic!("Error reading {}: {}", path, e));
+
+    let params_path = format!("{}.params", path);
+    let mut params = match fs::read_to_string(&params_path) {
+        Ok(contents) => params::parse_file(&contents)
+            .unwrap_or_else(|e| panic!("Error in {}: {}", params_path, e)),
+        Err(_) => Vec::new(),
+    };
+    if !overrides.is_empty() && registry.param_solver(puzzle).is_none() {
+        panic!("{} has no parameters", puzzle);
+    }
+    // e.g. a file for the example that only part 1 has parameters for
+    if !params.is_empty() && registry.param_solver(puzzle).is_none() {
+        let names: Vec<&str> = params.iter().map(|(name, _)| name.as_str()).collect();
+        eprintln!(
+            "Warning: {} has no parameters, ignoring {} from {}",
+            puzzle,
+            names.join(", "),
+            params_path
+        );
+        params.clear();
+    }
+    params.extend(overrides);
+
+    println!(
+        "Running Part {} of Day {} ({})",
+        puzzle.part, puzzle.day, puzzle.year
+    );
+    println!("========================");
+
+    // the solver with the progress bar, the main one, or the main one with parameters
+    let variant = if !params.is_empty() {
+        let params: Vec<String> = params
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        params.join(" ")
+    } else if registry.progress_solver(puzzle).is_some() {
+        "progress".to_string()
+    } else {
+        "default".to_string()
+    };
+    let mut cache = open_cache();
+    let key = cache_key(registry, puzzle, &variant, &input);
+    if options.use_cache {
+        if let Some(entry) = cached(&cache, &key) {
+            println!("Answer: {}", entry.answer);
+            println!("Time: {:?} (cached)", entry.time);
+            println!("Memory: {}", format_bytes(entry.memory));
+            return;
+        }
+    }
+
+    let (answer, time, memory) = match (registry.progress_solver(puzzle), params.is_empty()) {
+        // the progress solvers only know the default parameters
+        (Some(solver), true) => solve_with_progress(solver, &input, options.time_limit),
+        _ => {
+            let (answer, time, memory) = measure(|| match registry.param_solver(puzzle) {
+                Some(solver) => solver(&input, &params),
+                None => Ok(solver(&input)),
+            });
+            // bad parameters, or an overflow caught by the checked-arith feature
+            let answer = answer.unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            });
+            (answer, time, memory)
+        }
+    };
+    println!("Answer: {}", answer);
+    println!("Time: {:?}", time);
+    println!("Memory: {}", format_bytes(memory));
+
+    let entry = Entry {
+        answer: answer.clone(),
+        time,
+        memory,
+    };
+    save_to_cache(&mut cache, key, entry);
+
+    // examples and changed parameters aren't comparable with the other runs
+    if !example && params.is_empty() {
+        let revision = history::revision();
+        save_history(&[Record::new(&revision, puzzle, &answer, time, memory)]);
+    }
+}
+
+// runs the solver on its own thread while this one draws a progress bar.
+// a solver stopped by the time limit or Ctrl-C ends the program, there is no answer to show
+fn solve_with_progress(
+    solver: ProgressSolver,
+    input: &str,
+    time_limit: Option<Duration>,
+) -> (String, Duration, usize) {
+    let progress = Progress::new();
+    *RUNNING.lock().unwrap() = Some(progress.clone());
+    let draw = io::stderr().is_terminal();
+    let started = Instant::now();
+    let mut timed_out = false;
+
+    let (answer, time, memory) = thread::scope(|scope| {
+        let solving = scope.spawn(|| measure(|| solver(input, &progress)));
+        while !solving.is_finished() {
+            if !timed_out && time_limit.is_some_and(|limit| started.elapsed() >= limit) {
+                timed_out = true;
+                progress.cancel();
+            }
+            if draw {
+                draw_progress(&progress, started.elapsed());
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        // the panic has been printed already, all that's left is to say the run failed
+        solving.join().unwrap_or_else(|_| {
+            let failed = Stopped::Failed("the solver panicked".to_string());
+            (Err(failed), started.elapsed(), 0)
+        })
+    });
+
+    if draw {
+        eprint!("\r\x1b[K");
+    }
+    *RUNNING.lock().unwrap() = None;
+    match answer {
+        Ok(answer) => (answer, time, memory),
+        Err(Stopped::Failed(e)) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        Err(Stopped::Cancelled) => {
+            let reason = if timed_out {
+                "the time limit"
+            } else {
+                "Ctrl-C"
+            };
+            println!("Stopped by {} after {:?}", reason, time);
+            process::exit(if timed_out { 124 } else { 130 });
+        }
+    }
+}
+
+// "rows [########                ] 1234/4000001  12.3s"
+fn draw_progress(progress: &Progress, elapsed: Duration) {
+    const WIDTH: u64 = 30;
+    let (label, done, total) = progress.get();
+    let filled = (done * WIDTH).checked_div(total).unwrap_or(0).min(WIDTH) as usize;
+    eprint!(
+        "\r\x1b[K{} [{:<width$}] {}/{}  {:.1}s",
+        label,
+        "#".repeat(filled),
+        done,
+        total,
+        elapsed.as_secs_f64(),
+        width = WIDTH as usize
+    );
+    let _ = io::stderr().flush();
+}
+
+// solves the puzzle on the real input and writes the trace to `path` for `replay`
+pub fn trace(registry: &Registry, puzzle: Puzzle, path: &str) {
+    let Some(solver) = registry.traced(puzzle) else {
+        exit_with_error(&format!("{} can't be traced", puzzle));
+    };
+    let input = read_input(puzzle);
+    let file = fs::File::create(path).unwrap_or_else(|e| panic!("Error creating {}: {}", path, e));
+    let mut tracer = Tracer::new(BufWriter::new(file));
+    let answer = solver(&input, &mut tracer);
+    tracer
+        .finish()
+        .unwrap_or_else(|e| panic!("Error writing {}: {}", path, e));
+    println!("Answer: {}", answer);
+    println!("Trace written to {}", path);
+}
+
+pub fn list_parameters(registry: &Registry, year: i32, day: u32) {
+    let parameters = registry.parameters(year, day);
+    if parameters.is_empty() {
+        println!("Day {} ({}) has no parameters", day, year);
+    }
+    for (name, default) in parameters {
+        println!("{:<20} {}", name, default);
+    }
+}
+
+// days in `dir` replace the built in ones, plugins that can't be loaded are skipped
+pub fn load_plugins(registry: &mut Registry, dir: &str) {
+    let plugins = plugin::load_dir(Path::new(dir))
+        .unwrap_or_else(|e| panic!("Error reading plugins from {}: {}", dir, e));
+    for plugin in plugins {
+        match plugin {
+            Ok(plugin) => {
+                eprintln!(
+                    "Loaded day {} ({}) from {}",
+                    plugin.day(),
+                    plugin.year(),
+                    plugin.path().display()
+                );
+                registry.add_plugin(plugin);
+            }
+            Err(e) => eprintln!("Skipped: {}", e),
+        }
+    }
+}
+
+// solves every puzzle, or takes the answer from the cache, and writes a page with
+// the answers, the times and pictures of the days that can be traced to `path`
+pub fn report(registry: &Registry, path: &str, options: Options) {
+    let mut cache = open_cache();
+    let revision = history::revision();
+    let mut records = Vec::new();
+    let mut pictures = Vec::new();
+    for puzzle in registry.puzzles(None) {
+        let input = read_input(puzzle);
+        let key = cache_key(registry, puzzle, "default", &input);
+        // a day that can be traced is solved once, with the tracer on, for both its answer
+        // and its picture. tracing makes it slower, so that run isn't cached for `run`
+        let cached = cached(&cache, &key).filter(|_| options.use_cache);
+        let (entry, picture) = match (cached, registry.traced(puzzle)) {
+            (Some(entry), traced) => {
+                let picture = traced.and_then(|solver| report::picture(solver, &input).1);
+                (entry, picture)
+            }
+            (None, Some(solver)) => {
+                let ((answer, picture), time, memory) = measure(|| report::picture(solver, &input));
+                let entry = Entry {
+                    answer,
+                    time,
+                    memory,
+                };
+                (entry, picture)
+            }
+            (None, None) => {
+                let (answer, time, memory) = solve(registry.solver(puzzle).unwrap(), &input);
+                let entry = Entry {
+                    answer,
+                    time,
+                    memory,
+                };
+                save_to_cache(&mut cache, key, entry.clone());
+                (entry, None)
+            }
+        };
+        eprintln!("{:<24} {:>12.3?}", puzzle.to_string(), entry.time);
+        records.push(Record::new(
+            &revision,
+            puzzle,
+            &entry.answer,
+            entry.time,
+            entry.memory,
+        ));
+        if let Some(svg) = picture {
+            pictures.push((puzzle, svg));
+        }
+    }
+
+    fs::write(path, report::html(&records, &pictures))
+        .unwrap_or_else(|e| panic!("Error writing {}: {}", path, e));
+    println!("Report written to {}", path);
+}
+
+// the k elves carrying the most in an inventory of any size, read a line at a time
+#[cfg(feature = "day01")]
+pub fn calories(k: usize, path: Option<&str>) {
+    let time = Instant::now();
+    let summary = match path {
+        Some(path) => {
+            fs::File::open(path).and_then(|file| day01::read_top_k(io::BufReader::new(file), k))
+        }
+        None => day01::read_top_k(io::stdin().lock(), k),
+    };
+    let summary =
+        summary.unwrap_or_else(|e| panic!("Error reading {}: {}", path.unwrap_or("stdin"), e));
+    println!(
+        "{} elves carrying {} calories",
+        summary.elves, summary.total
+    );
+    for (rank, (id, calories)) in summary.top.iter().enumerate() {
+        println!("{:>4}. elf {:<10} {}", rank + 1, id, calories);
+    }
+    println!("Time: {:?}", time.elapsed());
+}
+
+// how evenly the elves' snacks could be shared out between them
+#[cfg(feature = "day01")]
+pub fn balance(path: Option<&str>) {
+    let path = path.map_or_else(|| Puzzle::new(2022, 1, 1).input_path(), str::to_string);
+    let input = input::read_path(&path).unwrap_or_else(|e| panic!("Error reading {}: {}", path, e));
+    let ledger: day01::CalorieLedger = input
+        .parse()
+        .unwrap_or_else(|e| panic!("Error in {}: {}", path, e));
+
+    let time = Instant::now();
+    let plan = ledger.plan();
+    let time = time.elapsed();
+
+    let before = ledger.totals();
+    let after = plan.totals();
+    let describe = |totals: &[u64]| {
+        format!(
+            "most {}, least {}, imbalance {}",
+            totals.iter().max().unwrap_or(&0),
+            totals.iter().min().unwrap_or(&0),
+            day01::imbalance(totals)
+        )
+    };
+    println!("Before: {}", describe(&before));
+    println!("After:  {}", describe(&after));
+    if plan.optimal {
+        println!("No plan can do better");
+    } else {
+        println!("The best plan found, a better one may exist");
+    }
+    println!("Time: {:?}", time);
+}
+
+// what the strategy guide scores for every reading of X, Y and Z,
+// and what each kind of round scores under each reading.
+// `overrides` are day 2's parameters, e.g. moves=5 for rock paper scissors lizard spock
+#[cfg(feature = "day02")]
+pub fn cipher(path: Option<&str>, overrides: &[(String, String)]) {
+    let path = path.map_or_else(|| Puzzle::new(2022, 2, 1).input_path(), str::to_string);
+    let input = input::read_path(&path).unwrap_or_else(|e| panic!("Error reading {}: {}", path, e));
+    let game = params::with_overrides::<day02::Params>(overrides)
+        .and_then(|params| params.game())
+        .unwrap_or_else(|e| exit_with_error(&e));
+    // every order of the moves is a reading, 7 moves already have 5040 of them
+    if game.names.len() > 7 {
+        exit_with_error(&format!(
+            "cipher tries every order of the moves, {} moves are too many",
+            game.names.len()
+        ));
+    }
+    let counts = day02::round_counts(&input, &game);
+    let interpretations = day02::analyse(&input, &game);
+    let letters = day02::our_letters(&game);
+
+    print!("{:<5}", "Rank");
+    for letter in &letters {
+        print!(" {:<9}", letter);
+    }
+    println!(" {:>7}", "Total");
+    for (rank, interpretation) in interpretations.iter().enumerate() {
+        print!("{:<5}", rank + 1);
+        for meaning in interpretation.decoding.meanings(&game) {
+            print!(" {:<9}", meaning);
+        }
+        println!(" {:>7}", interpretation.total);
+    }
+
+    // a column per kind of round, the points one such round is worth under each reading
+    let theirs = (b'A'..).map(char::from).take(letters.len());
+    let kinds: Vec<String> = theirs
+        .flat_map(|t| letters.iter().map(move |ours| format!("{} {}", t, ours)))
+        .collect();
+    println!();
+    println!("Points per round");
+    print!("{:<5}", "Rank");
+    for kind in kinds {
+        print!(" {:>5}", kind);
+    }
+    println!();
+    print!("{:<5}", "Count");
+    for count in counts {
+        print!(" {:>5}", count);
+    }
+    println!();
+    for (rank, interpretation) in interpretations.iter().enumerate() {
+        print!("{:<5}", rank + 1);
+        for score in &interpretation.round_scores {
+            print!(" {:>5}", score);
+        }
+        println!();
+    }
+}
+
+// every day 2 strategy against every other, the strategy guide from the input included
+#[cfg(feature = "day02")]
+pub fn tournament(rounds: usize, seed: u64) {
+    let input = read_input(Puzzle::new(2022, 2, 1));
+    let game = day02::Game::rock_paper_scissors();
+    let guide = day02::FixedGuide::from_input("strategy guide", &input, &game)
+        .unwrap_or_else(|e| exit_with_error(&e));
+    let strategies: Vec<Box<dyn day02::Strategy>> = vec![
+        Box::new(guide),
+        Box::new(day02::FrequencyCounter),
+        Box::new(day02::BeatLast),
+        Box::new(day02::Random),
+    ];
+    let standings = day02::tournament(&game, &strategies, rounds, seed);
+
+    println!("{} rounds per match, seed {}", rounds, seed);
+    println!(
+        "{:<5} {:<20} {:>4} {:>4} {:>4} {:>7} {:>10}",
+        "Rank", "Strategy", "W", "T", "L", "Points", "Score"
+    );
+    for (rank, standing) in standings.iter().enumerate() {
+        println!(
+            "{:<5} {:<20} {:>4} {:>4} {:>4} {:>7} {:>10}",
+            rank + 1,
+            standing.name,
+            standing.wins,
+            standing.ties,
+            standing.losses,
+            standing.points(),
+            standing.score
+        );
+    }
+}
+
+// how often each of the 13 interval relations comes up between the elves of a pair
+#[cfg(feature = "day04")]
+pub fn relations(path: Option<&str>) {
+    let path = path.map_or_else(|| Puzzle::new(2022, 4, 1).input_path(), str::to_string);
+    let input = input::read_path(&path).unwrap_or_else(|e| panic!("Error reading {}: {}", path, e));
+    let histogram = day04::histogram(&input);
+    let most = histogram.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    for (relation, count) in histogram {
+        // bars up to 50 wide
+        let bar = "#".repeat((count * 50).div_ceil(most.max(1)));
+        println!("{:<14} {:>6} {}", format!("{:?}", relation), count, bar);
+    }
+}
+
+// runs every puzzle once and prints how long each took
+pub fn bench(registry: &Registry, year: Option<i32>) {
+    let revision = history::revision();
+    let mut results = Vec::new();
+    for puzzle in registry.puzzles(year) {
+        let input = read_input(puzzle);
+        let (answer, time, memory) = solve(registry.solver(puzzle).unwrap(), &input);
+        results.push(Record::new(&revision, puzzle, &answer, time, memory));
+    }
+    save_history(&results);
+
+    println!();
+    println!("{:<24} {:>12} {:>12}", "Puzzle", "Time", "Memory");
+    for record in &results {
+        println!(
+            "{:<24} {:>12.3?} {:>12}",
+            record.puzzle.to_string(),
+            record.time,
+            format_bytes(record.memory)
+        );
+    }
+    let total: Duration = results.iter().map(|record| record.time).sum();
+    println!("{:<24} {:>12.3?}", "Total", total);
+}
+
+// checks every puzzle that has a known answer
+// returns false if any of them gave a different answer
+pub fn verify(registry: &Registry, year: Option<i32>) -> bool {
+    let revision = history::revision();
+    let mut results = Vec::new();
+    let mut records = Vec::new();
+    for puzzle in registry.puzzles(year) {
+        let Ok(expected) = fs::read_to_string(puzzle.answer_path()) else {
+            results.push((puzzle, "no answer"));
+            continue;
+        };
+        let input = read_input(puzzle);
+        let (answer, time, memory) = solve(registry.solver(puzzle).unwrap(), &input);
+        records.push(Record::new(&revision, puzzle, &answer, time, memory));
+        if answer.trim_end() == expected.trim_end() {
+            results.push((puzzle, "ok"));
+        } else {
+            results.push((puzzle, "WRONG"));
+        }
+    }
+
+    save_history(&records);
+
+    println!();
+    for (puzzle, result) in &results {
+        println!("{:<24} {}", puzzle.to_string(), result);
+    }
+    results.iter().all(|(_, result)| *result != "WRONG")
+}
+
+// runs every variant of a puzzle on the same input
+// returns false if they don't all agree with the main solver
+pub fn compare(registry: &Registry, puzzle: Puzzle) -> bool {
+    let variants = registry.variants(puzzle);
+    if variants.is_empty() {
+        eprintln!("Error: {} is not implemented yet", puzzle);
+        return false;
+    }
+    let input = read_input(puzzle);
+    let results: Vec<(&str, String, Duration)> = variants
+        .into_iter()
+        .map(|(name, solver)| {
+            let (answer, time, _) = solve(solver, &input);
+            (name, answer, time)
+        })
+        .collect();
+    let (_, expected, baseline) = results[0].clone();
+
+    println!();
+    println!("{}", puzzle);
+    println!("{:<20} {:>12} {:>10}  Answer", "Variant", "Time", "Speedup");
+    let mut agree = true;
+    for (name, answer, time) in &results {
+        let speedup = baseline.as_secs_f64() / time.as_secs_f64().max(f64::EPSILON);
+        let verdict = if *answer == expected {
+            ""
+        } else {
+            agree = false;
+            "  DISAGREES"
+        };
+        println!(
+            "{:<20} {:>12.3?} {:>9.2}x  {}{}",
+            name,
+            time,
+            speedup,
+            answer.lines().next().unwrap_or(""),
+            verdict
+        );
+    }
+    agree
+}
+
+// shows how each puzzle's time changed over the revisions it was run on, on this machine.
+// `threshold` is the slowdown in percent that counts as a regression.
+// returns false if any puzzle regressed
+pub fn history(threshold: f64) -> bool {
+    let records = history::load(HISTORY_PATH)
+        .unwrap_or_else(|e| panic!("Error reading {}: {}", HISTORY_PATH, e));
+    let machine = history::machine();
+    let trends = history::trends(&records, &machine, threshold / 100.0);
+    if trends.is_empty() {
+        println!(
+            "No history for {} yet, it is saved by run, bench and verify",
+            machine
+        );
+        return true;
+    }
+
+    println!(
+        "History on {}, fastest run of each revision, oldest first",
+        machine
+    );
+    println!();
+    println!(
+        "{:<24} {:<22} {:>12} {:>12} {:>8}  Trend",
+        "Puzzle", "Build", "Latest", "Memory", "Change"
+    );
+    let mut ok = true;
+    for trend in &trends {
+        let (_, latest, memory) = trend.revisions.last().unwrap();
+        let change = match &trend.revisions[..] {
+            [.., (_, previous, _), _] => format!(
+                "{:+.1}%",
+                (latest.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0
+            ),
+            _ => String::new(),
+        };
+        let recent: Vec<String> = trend
+            .revisions
+            .iter()
+            .rev()
+            .take(5)
+            .rev()
+            .map(|(revision, time, _)| format!("{} {:.1?}", revision, time))
+            .collect();
+        let mut flags = String::new();
+        if trend.regressed {
+            ok = false;
+            flags.push_str("  REGRESSED");
+        }
+        if trend.answer_changed {
+            flags.push_str("  ANSWER CHANGED");
+        }
+        // e.g. "release+checked-arith"
+        let build = std::iter::once(&trend.profile)
+            .chain(&trend.features)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("+");
+        println!(
+            "{:<24} {:<22} {:>12.3?} {:>12} {:>8}  {}{}",
+            trend.puzzle.to_string(),
+            build,
+            latest,
+            format_bytes(*memory),
+            change,
+            recent.join(", "),
+            flags
+        );
+    }
+    ok
+}
@@ -0,0 +1,311 @@
+use std::io::{self, Read};
+use std::net::SocketAddr;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::input::normalize;
+use progress::{Progress, Stopped};
+
+use crate::registry::{ProgressSolver, Puzzle, Registry, Solver};
+
+// the page at `/`, a form that posts the pasted input to the chosen puzzle
+const FORM: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Advent of Code</title></head>
+<body>
+<form id="form">
+  <input name="year" type="number" value="2022">
+  <input name="day" type="number" min="1" max="25" value="1">
+  <select name="part"><option>1</option><option>2</option></select>
+  <button>Solve</button><br>
+  <textarea name="input" rows="20" cols="80" placeholder="Paste your input here"></textarea>
+</form>
+<pre id="result"></pre>
+<script>
+document.getElementById("form").onsubmit = async (event) => {
+  event.preventDefault();
+  const form = event.target;
+  const url = `/${form.year.value}/${form.day.value}/${form.part.value}`;
+  const response = await fetch(url, { method: "POST", body: form.input.value });
+  document.getElementById("result").textContent = JSON.stringify(await response.json(), null, 2);
+};
+</script>
+</body>
+</html>
+"#;
+
+// the largest input accepted, well above any puzzle input
+const BODY_LIMIT: u64 = 10 * 1024 * 1024;
+
+// solves puzzles posted to /<year>/<day>/<part> with the input as the body
+// and answers with JSON, e.g.
+//   curl -X POST localhost:8080/2022/16/2 --data-binary @input/2022/day16.in
+pub struct Server {
+    http: Arc<tiny_http::Server>,
+    registry: Arc<Registry>,
+    time_limit: Duration,
+    workers: usize,
+    body_limit: u64,
+}
+
+impl Server {
+    // use port 0 to let the OS pick a free one, see `addr`
+    pub fn bind(addr: &str, registry: Registry, time_limit: Duration) -> io::Result<Self> {
+        let http = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+        Ok(Server {
+            http: Arc::new(http),
+            registry: Arc::new(registry),
+            time_limit,
+            workers: thread::available_parallelism().map_or(4, |n| n.get()),
+            body_limit: BODY_LIMIT,
+        })
+    }
+
+    // how many requests are handled at once, and how many solvers run at once
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    // inputs longer than this many bytes get a 413
+    pub fn with_body_limit(mut self, bytes: u64) -> Self {
+        self.body_limit = bytes;
+        self
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.http
+            .server_addr()
+            .to_ip()
+            .expect("Server is not listening on TCP")
+    }
+
+    // handles requests on `workers` threads until the process exits
+    pub fn run(self) {
+        let solvers = Arc::new(Pool::new(self.workers));
+        let handlers: Vec<_> = (0..self.workers)
+            .map(|_| {
+                let http = Arc::clone(&self.http);
+                let context = Context {
+                    registry: Arc::clone(&self.registry),
+                    solvers: Arc::clone(&solvers),
+                    time_limit: self.time_limit,
+                    body_limit: self.body_limit,
+                };
+                thread::spawn(move || {
+                    for request in http.incoming_requests() {
+                        handle(request, &context);
+                    }
+                })
+            })
+            .collect();
+        for handler in handlers {
+            let _ = handler.join();
+        }
+    }
+}
+
+// what a request handler needs from the server
+struct Context {
+    registry: Arc<Registry>,
+    solvers: Arc<Pool>,
+    time_limit: Duration,
+    body_limit: u64,
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+// A fixed number of threads running solvers. A solver that can't be stopped keeps its
+// thread after the request has had its 504, so rather than queueing a job behind those
+// `try_run` turns it down when every thread is busy.
+struct Pool {
+    jobs: Mutex<mpsc::Sender<Job>>,
+    idle: Arc<AtomicUsize>,
+}
+
+impl Pool {
+    fn new(threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let idle = Arc::new(AtomicUsize::new(threads));
+        for _ in 0..threads {
+            let receiver = Arc::clone(&receiver);
+            let idle = Arc::clone(&idle);
+            thread::spawn(move || loop {
+                let job = receiver.lock().unwrap().recv();
+                match job {
+                    Ok(job) => {
+                        job();
+                        idle.fetch_add(1, Ordering::SeqCst);
+                    }
+                    Err(_) => return,
+                }
+            });
+        }
+        Pool {
+            jobs: Mutex::new(sender),
+            idle,
+        }
+    }
+
+    // false if every thread is busy
+    fn try_run(&self, job: impl FnOnce() + Send + 'static) -> bool {
+        let claimed = self
+            .idle
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |idle| {
+                idle.checked_sub(1)
+            });
+        if claimed.is_err() {
+            return false;
+        }
+        self.jobs.lock().unwrap().send(Box::new(job)).is_ok()
+    }
+}
+
+fn handle(mut request: Request, context: &Context) {
+    let received = Instant::now();
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+
+    let response = if path == "/" {
+        if *request.method() == Method::Get {
+            Response::from_string(FORM)
+                .with_header(header("Content-Type", "text/html; charset=utf-8"))
+        } else {
+            error(405, "Use GET for the form")
+        }
+    } else {
+        match parse_path(&path) {
+            None => error(404, "Expected a path like /2022/16/2"),
+            Some(puzzle) => match context.registry.solver(puzzle) {
+                None => error(404, &format!("{} is not implemented", puzzle)),
+                Some(_) if *request.method() != Method::Post => {
+                    error(405, "POST the puzzle input to this path")
+                }
+                Some(solver) => {
+                    // one byte over the limit is enough to tell it's too long
+                    let mut input = String::new();
+                    let mut body = request.as_reader().take(context.body_limit + 1);
+                    match body.read_to_string(&mut input) {
+                        Err(_) => error(400, "The input must be UTF-8 text"),
+                        Ok(read) if read as u64 > context.body_limit => error(
+                            413,
+                            &format!("The input is longer than {} bytes", context.body_limit),
+                        ),
+                        Ok(_) => solve(
+                            puzzle,
+                            solver,
+                            context.registry.progress_solver(puzzle),
+                            normalize(&input),
+                            context,
+                            received,
+                        ),
+                    }
+                }
+            },
+        }
+    };
+
+    // the client may have given up already, nothing to do about that
+    let _ = request.respond(response);
+}
+
+// "/2022/16/2" -> Puzzle
+fn parse_path(path: &str) -> Option<Puzzle> {
+    let parts: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match parts[..] {
+        [year, day, part] => Some(Puzzle::new(
+            year.parse().ok()?,
+            day.parse().ok()?,
+            part.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
+
+// runs the solver on one of the pool's threads so a slow puzzle can't hold the request
+// forever. a solver that can be stopped is cancelled when it runs past the time limit,
+// any other keeps its thread until it finishes and the answer is thrown away
+fn solve(
+    puzzle: Puzzle,
+    solver: Solver,
+    stoppable: Option<ProgressSolver>,
+    input: String,
+    context: &Context,
+    received: Instant,
+) -> Response<io::Cursor<Vec<u8>>> {
+    let time_limit = context.time_limit;
+    let can_stop = stoppable.is_some();
+    let (sender, receiver) = mpsc::channel();
+    let progress = Progress::new();
+    let cancel = progress.clone();
+    let started = context.solvers.try_run(move || {
+        let time = Instant::now();
+        let answer = panic::catch_unwind(AssertUnwindSafe(|| match stoppable {
+            Some(solver) => solver(&input, &progress),
+            None => Ok(solver(&input)),
+        }));
+        // a panic or a cancelled solver sends nothing
+        match answer {
+            Ok(Ok(answer)) => {
+                let _ = sender.send(Ok((answer, time.elapsed())));
+            }
+            Ok(Err(Stopped::Failed(e))) => {
+                let _ = sender.send(Err(e));
+            }
+            _ => {}
+        }
+    });
+    if !started {
+        return error(
+            503,
+            "Every solver is busy, some may still be finishing puzzles that ran out of time",
+        );
+    }
+
+    match receiver.recv_timeout(time_limit) {
+        Ok(Ok((answer, time))) => json_response(
+            200,
+            json!({
+                "year": puzzle.year,
+                "day": puzzle.day,
+                "part": puzzle.part,
+                "answer": answer,
+                "solve_ms": time.as_secs_f64() * 1000.0,
+                "total_ms": received.elapsed().as_secs_f64() * 1000.0,
+            }),
+        ),
+        Ok(Err(e)) => error(422, &format!("{} failed: {}", puzzle, e)),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            let mut message = format!("{} took longer than {:?}", puzzle, time_limit);
+            if !can_stop {
+                message += ", it can't be stopped so it keeps a solver busy until it finishes";
+            }
+            error(504, &message)
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => error(
+            422,
+            &format!("{} failed, the input is probably malformed", puzzle),
+        ),
+    }
+}
+
+fn error(status: u16, message: &str) -> Response<io::Cursor<Vec<u8>>> {
+    json_response(status, json!({ "error": message }))
+}
+
+fn json_response(status: u16, body: Value) -> Response<io::Cursor<Vec<u8>>> {
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name, value).unwrap()
+}
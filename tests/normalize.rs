@@ -0,0 +1,90 @@
+// Every day's input saved with Windows line endings, a trailing newline, a byte order mark
+// or trailing spaces has to give the same answers as the clean input. The solvers don't
+// handle any of that themselves, the runner reads every input file through
+// `input::read_path`, which normalises it, so that's how the answers are checked here too.
+
+use std::fs;
+
+use advent_of_code_2022::input::{self, normalize};
+use advent_of_code_2022::registry::{Puzzle, Registry};
+
+fn variants(clean: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("trailing newline", format!("{}\n", clean)),
+        ("trailing blank lines", format!("{}\n\n\n", clean)),
+        ("crlf", format!("{}\r\n", clean.replace('\n', "\r\n"))),
+        ("bom", format!("\u{feff}{}", clean)),
+        ("trailing spaces", clean.replace('\n', " \n") + " \t"),
+    ]
+}
+
+// `parts` are the parts fast enough to run in a debug build,
+// the slow ones are still covered by the normalised text being identical
+fn check(day: u32, parts: &[u32]) {
+    let puzzle = Puzzle::new(2022, day, 1);
+    let clean = input::read(puzzle).unwrap();
+    for (name, variant) in variants(&clean) {
+        assert!(
+            normalize(&variant) == clean,
+            "day {} with {} differs",
+            day,
+            name
+        );
+    }
+
+    // the raw file, read the way `run` reads it
+    let crlf = format!("\u{feff}{}\r\n\r\n", clean.replace('\n', "\r\n"));
+    let path =
+        std::env::temp_dir().join(format!("aoc-crlf-{}-day{:02}.in", std::process::id(), day));
+    fs::write(&path, crlf).unwrap();
+    let read = input::read_path(path.to_str().unwrap());
+    let _ = fs::remove_file(&path);
+    let read = read.unwrap();
+
+    let registry = Registry::with_all_years();
+    for &part in parts {
+        let puzzle = Puzzle::new(2022, day, part);
+        // days left out of the build have nothing to check
+        let Some(solver) = registry.solver(puzzle) else {
+            continue;
+        };
+        let expected = fs::read_to_string(puzzle.answer_path()).unwrap();
+        let answer = solver(&read);
+        assert_eq!(answer.trim_end(), expected.trim_end(), "{}", puzzle);
+    }
+}
+
+macro_rules! days {
+    ($($name:ident: $day:expr, $parts:expr;)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check($day, &$parts);
+            }
+        )*
+    };
+}
+
+days! {
+    day01: 1, [1, 2];
+    day02: 2, [1, 2];
+    day03: 3, [1, 2];
+    day04: 4, [1, 2];
+    day05: 5, [1, 2];
+    day06: 6, [1, 2];
+    day07: 7, [1, 2];
+    day08: 8, [1, 2];
+    day09: 9, [1, 2];
+    day10: 10, [1, 2];
+    day11: 11, [1, 2];
+    day12: 12, [1];
+    day13: 13, [1, 2];
+    day14: 14, [1];
+    day15: 15, [1];
+    day16: 16, [1];
+    day17: 17, [1];
+    day18: 18, [1, 2];
+    day19: 19, [];
+    day20: 20, [1, 2];
+    day21: 21, [1, 2];
+}
@@ -0,0 +1,136 @@
+// Starts the server on a free localhost port and talks plain HTTP to it.
+#![cfg(feature = "serve")]
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code_2022::registry::{Registry, Year};
+use advent_of_code_2022::server::Server;
+
+fn start(registry: Registry, time_limit: Duration) -> SocketAddr {
+    serve(bind(registry, time_limit))
+}
+
+fn bind(registry: Registry, time_limit: Duration) -> Server {
+    Server::bind("127.0.0.1:0", registry, time_limit).unwrap()
+}
+
+fn serve(server: Server) -> SocketAddr {
+    let addr = server.addr();
+    thread::spawn(move || server.run());
+    addr
+}
+
+// returns the status code and the body
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response[9..12].parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, body.to_string())
+}
+
+fn json(body: &str) -> serde_json::Value {
+    serde_json::from_str(body).unwrap()
+}
+
+#[test]
+fn solves_posted_input() {
+    let addr = start(Registry::with_all_years(), Duration::from_secs(10));
+    let (status, body) = request(addr, "POST", "/2022/2/1", "A Y\nB X\nC Z");
+    assert_eq!(status, 200);
+    let body = json(&body);
+    assert_eq!(body["answer"], "15");
+    assert_eq!(body["day"], 2);
+    assert!(body["solve_ms"].as_f64().unwrap() <= body["total_ms"].as_f64().unwrap());
+}
+
+#[test]
+fn rejects_unknown_puzzles() {
+    let addr = start(Registry::with_all_years(), Duration::from_secs(10));
+    assert_eq!(request(addr, "POST", "/2015/1/1", "").0, 404);
+    assert_eq!(request(addr, "POST", "/2022/day2", "").0, 404);
+    assert_eq!(request(addr, "GET", "/2022/2/1", "").0, 405);
+}
+
+#[test]
+fn reports_malformed_input() {
+    let addr = start(Registry::with_all_years(), Duration::from_secs(10));
+    let (status, body) = request(addr, "POST", "/2022/2/1", "not a strategy guide");
+    assert_eq!(status, 422);
+    assert!(json(&body)["error"]
+        .as_str()
+        .unwrap()
+        .contains("2022 day 02 part 1"));
+}
+
+#[test]
+fn enforces_time_limit() {
+    fn slow(_: &str) -> String {
+        thread::sleep(Duration::from_secs(5));
+        "too late".to_string()
+    }
+    let mut registry = Registry::default();
+    registry.add_year(Year {
+        year: 2022,
+        solutions: vec![(1, slow, slow)],
+        ..Year::default()
+    });
+
+    let addr = start(registry, Duration::from_millis(100));
+    let (status, _) = request(addr, "POST", "/2022/1/1", "");
+    assert_eq!(status, 504);
+}
+
+#[test]
+fn turns_down_work_while_solvers_are_busy() {
+    fn slow(_: &str) -> String {
+        thread::sleep(Duration::from_secs(5));
+        "too late".to_string()
+    }
+    let mut registry = Registry::default();
+    registry.add_year(Year {
+        year: 2022,
+        solutions: vec![(1, slow, slow)],
+        ..Year::default()
+    });
+
+    // the only solver is still sleeping after the first request's 504
+    let addr = serve(bind(registry, Duration::from_millis(100)).with_workers(1));
+    let (status, body) = request(addr, "POST", "/2022/1/1", "");
+    assert_eq!(status, 504);
+    assert!(json(&body)["error"]
+        .as_str()
+        .unwrap()
+        .contains("can't be stopped"));
+    assert_eq!(request(addr, "POST", "/2022/1/2", "").0, 503);
+}
+
+#[test]
+fn rejects_long_inputs() {
+    let server = bind(Registry::with_all_years(), Duration::from_secs(10)).with_body_limit(8);
+    let addr = serve(server);
+    assert_eq!(request(addr, "POST", "/2022/2/1", "A Y\nB X\nC Z").0, 413);
+    assert_eq!(request(addr, "POST", "/2022/2/1", "A Y\nB X").0, 200);
+}
+
+#[test]
+fn serves_the_form() {
+    let addr = start(Registry::with_all_years(), Duration::from_secs(10));
+    let (status, body) = request(addr, "GET", "/", "");
+    assert_eq!(status, 200);
+    assert!(body.contains("<form"));
+}
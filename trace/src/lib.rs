@@ -0,0 +1,140 @@
+// Structured events from inside a solver, written as one JSON object per line, e.g.
+//   {"step":12,"event":"sand_at_rest","x":500,"y":8,"glyph":"o"}
+// The runner's `replay` command draws a trace step by step.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+pub struct Tracer {
+    out: Option<Box<dyn Write>>,
+    step: usize,
+    error: Option<io::Error>,
+}
+
+impl Tracer {
+    // a tracer that throws everything away, for normal runs
+    pub fn off() -> Self {
+        Tracer {
+            out: None,
+            step: 0,
+            error: None,
+        }
+    }
+
+    pub fn new(out: impl Write + 'static) -> Self {
+        Tracer {
+            out: Some(Box::new(out)),
+            step: 0,
+            error: None,
+        }
+    }
+
+    // lets a solver skip work that only matters for the trace
+    pub fn is_on(&self) -> bool {
+        self.out.is_some()
+    }
+
+    // something that happened, replay shows it as a line of text
+    pub fn event(&mut self, name: &str, fields: &[(&str, i64)]) {
+        if self.is_on() {
+            self.write(name, fields, None);
+        }
+    }
+
+    // something that happened at (x, y), replay draws `glyph` there and keeps it for the rest of the replay
+    pub fn mark(&mut self, name: &str, x: i64, y: i64, glyph: char) {
+        if self.is_on() {
+            self.write(name, &[("x", x), ("y", y)], Some(glyph));
+        }
+    }
+
+    // tells replay that y grows upwards in this puzzle, so the grid is drawn upside down
+    pub fn y_up(&mut self) {
+        self.event("y_up", &[]);
+    }
+
+    // flushes the output and reports the first error that happened while tracing,
+    // the solver itself never sees them
+    pub fn finish(mut self) -> io::Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        match self.out.as_mut() {
+            Some(out) => out.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn write(&mut self, name: &str, fields: &[(&str, i64)], glyph: Option<char>) {
+        self.step += 1;
+        let mut line = format!("{{\"step\":{},\"event\":{}", self.step, quote(name));
+        for (key, value) in fields {
+            write!(line, ",{}:{}", quote(key), value).unwrap();
+        }
+        if let Some(glyph) = glyph {
+            write!(line, ",\"glyph\":{}", quote(&glyph.to_string())).unwrap();
+        }
+        line.push('}');
+
+        if self.error.is_some() {
+            return;
+        }
+        if let Some(out) = self.out.as_mut() {
+            if let Err(e) = writeln!(out, "{}", line) {
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+// a JSON string
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // a writer the test can still read after handing it to the tracer
+    #[derive(Clone, Default)]
+    struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let out = Shared::default();
+        let mut tracer = Tracer::new(out.clone());
+        tracer.event("register", &[("cycle", 20), ("x", -3)]);
+        tracer.mark("sand_at_rest", 500, 8, '"');
+        tracer.finish().unwrap();
+
+        let written = String::from_utf8(out.0.borrow().clone()).unwrap();
+        assert_eq!(
+            written,
+            "{\"step\":1,\"event\":\"register\",\"cycle\":20,\"x\":-3}\n\
+             {\"step\":2,\"event\":\"sand_at_rest\",\"x\":500,\"y\":8,\"glyph\":\"\\\"\"}\n"
+        );
+    }
+}
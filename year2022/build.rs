@@ -0,0 +1,73 @@
+// hashes every day's sources into `sources.rs`, so the runner's answer cache
+// can tell when a day, or a crate it shares with the others, has changed since
+// its answer was cached
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 64 bit FNV-1a, stable across Rust versions unlike the std hasher
+fn hash(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn files(dir: &Path, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files(&path, found);
+        } else {
+            found.push(path);
+        }
+    }
+}
+
+// the crate's Cargo.toml and sources, and tells cargo to build again when they change
+fn hash_crate(hash_so_far: u64, dir: &Path) -> u64 {
+    let mut found = vec![dir.join("Cargo.toml")];
+    files(&dir.join("src"), &mut found);
+    // sorted so the hash doesn't depend on the order the OS lists the files in
+    found.sort();
+
+    let mut crate_hash = hash_so_far;
+    for path in found {
+        let relative = path.strip_prefix(dir).unwrap();
+        crate_hash = hash(crate_hash, relative.to_string_lossy().as_bytes());
+        crate_hash = hash(crate_hash, &fs::read(&path).unwrap_or_default());
+    }
+    // cargo checks everything inside a directory for changes
+    println!("cargo:rerun-if-changed={}", dir.join("src").display());
+    println!(
+        "cargo:rerun-if-changed={}",
+        dir.join("Cargo.toml").display()
+    );
+    crate_hash
+}
+
+fn main() {
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+
+    // the crates the days share can change an answer too, and so can checked-arith
+    // turning an overflow into an error
+    let mut shared = 0xcbf29ce484222325;
+    for name in ["params", "progress", "trace"] {
+        shared = hash_crate(shared, &PathBuf::from(format!("../{}", name)));
+    }
+    let checked = std::env::var("CARGO_FEATURE_CHECKED_ARITH").is_ok();
+    shared = hash(shared, &[checked as u8]);
+
+    let mut sources = String::from("pub fn sources() -> Vec<(u32, u64)> {\n    vec![\n");
+    for day in 1..=25 {
+        // only the days this crate is built with, see the features in Cargo.toml
+        if std::env::var(format!("CARGO_FEATURE_DAY{:02}", day)).is_err() {
+            continue;
+        }
+        let day_hash = hash_crate(shared, &PathBuf::from(format!("../day{:02}", day)));
+        sources += &format!("        ({}, {:#x}),\n", day, day_hash);
+    }
+    sources += "    ]\n}\n";
+    fs::write(Path::new(&out_dir).join("sources.rs"), sources).unwrap();
+}
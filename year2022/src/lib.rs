@@ -0,0 +1,255 @@
+// Everything the runner needs to know about the 2022 puzzles.
+// Another year is added by making a crate with the same items
+// and registering it in the runner's `Registry::with_all_years`.
+// Each day is behind a feature of the same name, so the lists below
+// only hold the days this crate was built with.
+
+pub const YEAR: i32 = 2022;
+
+// takes the puzzle input and returns the answer
+pub type Solver = fn(&str) -> String;
+
+// takes the puzzle input and draws the simulation one frame at a time,
+// see `registry::Animation` in the runner
+pub type Animation = fn(&str, &mut dyn FnMut(&str, usize) -> bool);
+
+// takes the puzzle input and `name=value` overrides for the day's parameters
+pub type ParamSolver = fn(&str, &[(String, String)]) -> Result<String, String>;
+
+// the names and default values of a day's parameters
+pub type Defaults = fn() -> Vec<(&'static str, String)>;
+
+// (day, parameters, [(part, solver)])
+pub type DayParameters = (u32, Defaults, Vec<(u32, ParamSolver)>);
+
+// takes the puzzle input and writes what happens while solving it to the tracer
+pub type TracedSolver = fn(&str, &mut trace::Tracer) -> String;
+
+// takes the puzzle input and reports how far along it is, stops when cancelled
+pub type ProgressSolver = fn(&str, &progress::Progress) -> Result<String, progress::Stopped>;
+
+// (day, part 1, part 2)
+pub fn solutions() -> Vec<(u32, Solver, Solver)> {
+    vec![
+        #[cfg(feature = "day01")]
+        (1, day01::part1, day01::part2),
+        #[cfg(feature = "day02")]
+        (2, day02::part1, day02::part2),
+        #[cfg(feature = "day03")]
+        (3, day03::part1, day03::part2),
+        #[cfg(feature = "day04")]
+        (4, day04::part1, day04::part2),
+        #[cfg(feature = "day05")]
+        (5, day05::part1, day05::part2),
+        #[cfg(feature = "day06")]
+        (6, day06::part1, day06::part2),
+        #[cfg(feature = "day07")]
+        (7, day07::part1, day07::part2),
+        #[cfg(feature = "day08")]
+        (8, day08::part1, day08::part2),
+        #[cfg(feature = "day09")]
+        (9, day09::part1, day09::part2),
+        #[cfg(feature = "day10")]
+        (10, day10::part1, day10::part2),
+        #[cfg(feature = "day11")]
+        (11, day11::part1, day11::part2),
+        #[cfg(feature = "day12")]
+        (12, day12::part1, day12::part2),
+        #[cfg(feature = "day13")]
+        (13, day13::part1, day13::part2),
+        #[cfg(feature = "day14")]
+        (14, day14::part1, day14::part2),
+        #[cfg(feature = "day15")]
+        (15, day15::part1, day15::part2),
+        #[cfg(feature = "day16")]
+        (16, day16::part1, day16::part2),
+        #[cfg(feature = "day17")]
+        (17, day17::part1, day17::part2),
+        #[cfg(feature = "day18")]
+        (18, day18::part1, day18::part2),
+        #[cfg(feature = "day19")]
+        (19, day19::part1, day19::part2),
+        #[cfg(feature = "day20")]
+        (20, day20::part1, day20::part2),
+        #[cfg(feature = "day21")]
+        (21, day21::part1, day21::part2),
+    ]
+}
+
+// other ways of solving a part, compared against the main solver by `compare`
+// (day, part, name, solver)
+pub fn variants() -> Vec<(u32, u32, &'static str, Solver)> {
+    vec![
+        #[cfg(feature = "day03")]
+        (3, 2, "shuffled", day03::part2_shuffled),
+        #[cfg(feature = "day12")]
+        (12, 2, "multi-source bfs", day12::part2_multi_source),
+        #[cfg(feature = "day20")]
+        (20, 1, "treap", day20::part1_treap),
+        #[cfg(feature = "day20")]
+        (20, 2, "treap", day20::part2_treap),
+    ]
+}
+
+// turns a day's `partN_with(input, &Params)` into a `ParamSolver`,
+// or with `try` its `try_partN_with` that returns the overflows checked-arith finds as errors
+#[allow(unused_macros)] // when none of the days with parameters are built
+macro_rules! with_params {
+    ($day:ident::$solver:ident) => {
+        |input: &str, overrides: &[(String, String)]| -> Result<String, String> {
+            let params = params::with_overrides::<$day::Params>(overrides)?;
+            Ok($day::$solver(input, &params))
+        }
+    };
+    (try $day:ident::$solver:ident) => {
+        |input: &str, overrides: &[(String, String)]| -> Result<String, String> {
+            let params = params::with_overrides::<$day::Params>(overrides)?;
+            $day::$solver(input, &params)
+        }
+    };
+}
+
+// days with numbers from the puzzle text that differ for the examples,
+// or that are worth changing to see what happens
+pub fn parameters() -> Vec<DayParameters> {
+    vec![
+        #[cfg(feature = "day01")]
+        (
+            1,
+            params::defaults::<day01::Params>,
+            vec![(2, with_params!(day01::part2_with))],
+        ),
+        #[cfg(feature = "day02")]
+        (
+            2,
+            params::defaults::<day02::Params>,
+            vec![
+                (1, with_params!(try day02::try_part1_with)),
+                (2, with_params!(try day02::try_part2_with)),
+            ],
+        ),
+        #[cfg(feature = "day03")]
+        (
+            3,
+            params::defaults::<day03::Params>,
+            vec![(2, with_params!(try day03::try_part2_with))],
+        ),
+        #[cfg(feature = "day07")]
+        (
+            7,
+            params::defaults::<day07::Params>,
+            vec![(2, with_params!(day07::part2_with))],
+        ),
+        #[cfg(feature = "day11")]
+        (
+            11,
+            params::defaults::<day11::Params>,
+            vec![
+                (1, with_params!(try day11::try_part1_with)),
+                (2, with_params!(try day11::try_part2_with)),
+            ],
+        ),
+        #[cfg(feature = "day15")]
+        (
+            15,
+            params::defaults::<day15::Params>,
+            vec![
+                (1, with_params!(day15::part1_with)),
+                (2, with_params!(try day15::try_part2_with)),
+            ],
+        ),
+        #[cfg(feature = "day16")]
+        (
+            16,
+            params::defaults::<day16::Params>,
+            vec![
+                (1, with_params!(day16::part1_with)),
+                (2, with_params!(day16::part2_with)),
+            ],
+        ),
+        #[cfg(feature = "day17")]
+        (
+            17,
+            params::defaults::<day17::Params>,
+            vec![
+                (1, with_params!(day17::part1_with)),
+                (2, with_params!(day17::part2_with)),
+            ],
+        ),
+        #[cfg(feature = "day19")]
+        (
+            19,
+            params::defaults::<day19::Params>,
+            vec![
+                (1, with_params!(day19::part1_with)),
+                (2, with_params!(day19::part2_with)),
+            ],
+        ),
+        #[cfg(feature = "day20")]
+        (
+            20,
+            params::defaults::<day20::Params>,
+            vec![(2, with_params!(try day20::try_part2_with))],
+        ),
+    ]
+}
+
+// solvers that can write a trace for `replay`, (day, part, solver)
+pub fn traced() -> Vec<(u32, u32, TracedSolver)> {
+    vec![
+        #[cfg(feature = "day10")]
+        (10, 1, day10::part1_traced),
+        #[cfg(feature = "day10")]
+        (10, 2, day10::part2_traced),
+        #[cfg(feature = "day12")]
+        (12, 1, day12::part1_traced),
+        #[cfg(feature = "day14")]
+        (14, 1, day14::part1_traced),
+        #[cfg(feature = "day14")]
+        (14, 2, day14::part2_traced),
+        #[cfg(feature = "day17")]
+        (17, 1, day17::part1_traced),
+        #[cfg(feature = "day18")]
+        (18, 2, day18::part2_traced),
+    ]
+}
+
+// turns a day's `partN_progress(input, &Params, &Progress)` into a `ProgressSolver`
+// that uses the default parameters. the day can fail with `Cancelled` or `Stopped`
+#[allow(unused_macros)]
+macro_rules! with_progress {
+    ($day:ident::$solver:ident) => {
+        |input: &str, progress: &progress::Progress| {
+            $day::$solver(input, &$day::Params::default(), progress)
+                .map_err(progress::Stopped::from)
+        }
+    };
+}
+
+// the slow parts, which report their progress and can be stopped, (day, part, solver)
+pub fn progress() -> Vec<(u32, u32, ProgressSolver)> {
+    vec![
+        #[cfg(feature = "day15")]
+        (15, 2, with_progress!(day15::part2_progress)),
+        #[cfg(feature = "day16")]
+        (16, 2, with_progress!(day16::part2_progress)),
+        #[cfg(feature = "day19")]
+        (19, 1, with_progress!(day19::part1_progress)),
+        #[cfg(feature = "day19")]
+        (19, 2, with_progress!(day19::part2_progress)),
+    ]
+}
+
+// (day, hash of the day's crate), written by build.rs
+include!(concat!(env!("OUT_DIR"), "/sources.rs"));
+
+pub fn animations() -> Vec<(u32, Animation)> {
+    vec![
+        #[cfg(feature = "day10")]
+        (10, day10::animate),
+        #[cfg(feature = "day14")]
+        (14, day14::animate),
+        #[cfg(feature = "day17")]
+        (17, day17::animate),
+    ]
+}